@@ -0,0 +1,29 @@
+// `--generate <seed> [--size N] [--difficulty N]` swaps a day's puzzle input
+// for one made by `generate`, with the day's own default size and difficulty
+pub fn generated(
+    args: &[String],
+    size: usize,
+    difficulty: usize,
+    generate: impl FnOnce(u64, usize, usize) -> String,
+) -> Result<Option<String>, String> {
+    let Some(i) = args.iter().position(|arg| arg == "--generate") else {
+        return Ok(None);
+    };
+    let usage = "usage: --generate <seed> [--size N] [--difficulty N]";
+    let seed = args
+        .get(i + 1)
+        .and_then(|seed| seed.parse().ok())
+        .ok_or(usage)?;
+    let option = |name: &str, default| match args.iter().position(|arg| arg == name) {
+        Some(i) => args
+            .get(i + 1)
+            .and_then(|value| value.parse().ok())
+            .ok_or(usage),
+        None => Ok(default),
+    };
+    Ok(Some(generate(
+        seed,
+        option("--size", size)?,
+        option("--difficulty", difficulty)?,
+    )))
+}
@@ -0,0 +1,46 @@
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+
+const WORDS: [&str; 9] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+// Spelled out digits sharing a letter, which only count once each way
+const OVERLAPS: [&str; 8] = [
+    "oneight",
+    "twone",
+    "threeight",
+    "fiveight",
+    "sevenine",
+    "eightwo",
+    "eighthree",
+    "nineight",
+];
+
+// `size` lines, `difficulty` out of 10 is how often a line gets overlapping
+// words; every line keeps at least one real digit
+pub fn generate(seed: u64, size: usize, difficulty: usize) -> String {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut input = String::new();
+    for _ in 0..size {
+        let mut pieces = Vec::new();
+        pieces.push(rng.gen_range(1..=9).to_string());
+        for _ in 0..rng.gen_range(1..=6) {
+            pieces.push(match rng.gen_range(0..3) {
+                0 => rng.gen_range(1..=9).to_string(),
+                1 => WORDS.choose(&mut rng).unwrap().to_string(),
+                _ => (0..rng.gen_range(1..=4))
+                    .map(|_| rng.gen_range(b'a'..=b'z') as char)
+                    .collect(),
+            });
+        }
+        if rng.gen_range(0..10) < difficulty {
+            pieces.push(OVERLAPS.choose(&mut rng).unwrap().to_string());
+        }
+        pieces.shuffle(&mut rng);
+        input.push_str(&pieces.concat());
+        input.push('\n');
+    }
+    input
+}
@@ -3,17 +3,26 @@ use nom::bytes::complete::tag;
 use nom::combinator::map;
 use nom::IResult;
 
-fn main() {
-    let input = include_str!("../input.txt");
+mod generate;
+
+fn main() -> Result<(), String> {
+    let args = std::env::args().collect::<Vec<_>>();
+    let generated = common::generated(&args, 1000, 5, generate::generate)?;
+    let input = generated.as_deref().unwrap_or(include_str!("../input.txt"));
+    if args.iter().any(|arg| arg == "--print") {
+        print!("{}", input);
+        return Ok(());
+    }
     let now = std::time::Instant::now();
     let part1 = calibration_value(input);
     println!("Part 1: {} [{:?}]", part1, now.elapsed());
     let now = std::time::Instant::now();
     let part2 = part2(input);
     println!("Part 2: {} [{:?}]", part2, now.elapsed());
+    Ok(())
 }
 
-fn calibration_value(s: &str) -> u32 {
+pub(crate) fn calibration_value(s: &str) -> u32 {
     let mut values = Vec::new();
     for line in s.lines().map(|l| l.chars().collect::<Vec<char>>()) {
         let mut i: usize = 0;
@@ -63,7 +72,7 @@ fn num(s: &str) -> IResult<&str, u32, ()> {
     )(s)
 }
 
-fn part2(s: &str) -> u32 {
+pub(crate) fn part2(s: &str) -> u32 {
     let mut values = Vec::new();
     for line in s.lines().map(|l| l.chars().collect::<Vec<char>>()) {
         let mut i: usize = 0;
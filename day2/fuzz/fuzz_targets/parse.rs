@@ -0,0 +1,17 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+#[allow(dead_code)]
+#[path = "../../src/main.rs"]
+mod day2;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(input) = std::str::from_utf8(data) {
+        for game in day2::split(input) {
+            for set in game {
+                set.for_each(drop);
+            }
+        }
+    }
+});
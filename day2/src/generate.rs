@@ -0,0 +1,28 @@
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+
+const COLOURS: [&str; 3] = ["red", "green", "blue"];
+
+// `size` games, each with up to `difficulty` + 1 sets of up to 4 * `difficulty`
+// cubes of a colour, so more games are impossible as it goes up
+pub fn generate(seed: u64, size: usize, difficulty: usize) -> String {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let most = (4 * difficulty).max(1);
+    let mut input = String::new();
+    for id in 1..=size {
+        let sets = (0..rng.gen_range(1..=difficulty + 1))
+            .map(|_| {
+                let mut colours = COLOURS;
+                colours.shuffle(&mut rng);
+                colours[..rng.gen_range(1..=3)]
+                    .iter()
+                    .map(|colour| format!("{} {}", rng.gen_range(1..=most), colour))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            })
+            .collect::<Vec<_>>();
+        input.push_str(&format!("Game {}: {}\n", id, sets.join("; ")));
+    }
+    input
+}
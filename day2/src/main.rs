@@ -1,19 +1,29 @@
-use std::{iter::Map, str::{Lines, Split}};
+mod generate;
 
-fn main() {
+fn main() -> Result<(), String> {
+    let args = std::env::args().collect::<Vec<_>>();
+    let generated = common::generated(&args, 100, 5, generate::generate)?;
+    let input = generated.as_deref().unwrap_or(INPUT);
+    if args.iter().any(|arg| arg == "--print") {
+        print!("{}", input);
+        return Ok(());
+    }
     let now = std::time::Instant::now();
-    let result = part1(INPUT, [12, 13, 14]);
+    let result = part1(input, [12, 13, 14]);
     println!("Part 1: {} [{:?}]", result, now.elapsed());
     let now = std::time::Instant::now();
-    let result = part2(INPUT);
+    let result = part2(input);
     println!("Part 2: {} [{:?}]", result, now.elapsed());
+    Ok(())
 }
 
 const INPUT: &str = include_str!("../input.txt");
 
-fn split(input: &str) -> Map<Lines, fn(&str) -> Map<Split<'_, &str>, fn(&str) -> Map<Split<'_, &str>, fn(&str) -> (u32, &str)>>> {
+pub(crate) fn split(
+    input: &str,
+) -> impl Iterator<Item = impl Iterator<Item = impl Iterator<Item = (u32, &str)>>> {
     input.lines().map(|line| {
-        line.split(": ").skip(1).next().unwrap().split("; ").map(|set| {
+        line.split(": ").nth(1).unwrap().split("; ").map(|set| {
             set.split(", ").map(|part| {
                 let mut parts = part.splitn(2, ' ');
                 (
@@ -25,17 +35,16 @@ fn split(input: &str) -> Map<Lines, fn(&str) -> Map<Split<'_, &str>, fn(&str) ->
     })
 }
 
-fn part1(input: &str, available: [u32; 3]) -> u32 {
+pub(crate) fn part1(input: &str, available: [u32; 3]) -> u32 {
     let mut total = 0;
     let input = split(input);
     'uwu: for (idx, line) in input.enumerate() {
-        let mut valid = true;
         for set in line {
             for (count, color) in set {
-                match color {
-                    "red" => valid = available[0] >= count,
-                    "green" => valid = available[1] >= count,
-                    "blue" => valid = available[2] >= count,
+                let valid = match color {
+                    "red" => available[0] >= count,
+                    "green" => available[1] >= count,
+                    "blue" => available[2] >= count,
                     _ => panic!("Unknown color: {}", color),
                 };
                 if !valid {
@@ -48,7 +57,7 @@ fn part1(input: &str, available: [u32; 3]) -> u32 {
     total as u32
 }
 
-fn part2(input: &str) -> u32 {
+pub(crate) fn part2(input: &str) -> u32 {
     let mut total = 0;
     let input = split(input);
     for line in input {
@@ -69,4 +78,4 @@ fn part2(input: &str) -> u32 {
         total += max_red * max_green * max_blue;
     }
     total
-}
\ No newline at end of file
+}
@@ -0,0 +1,36 @@
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+
+const SYMBOLS: [char; 10] = ['*', '*', '*', '#', '+', '$', '/', '@', '%', '='];
+
+// A `size` by `size` schematic, `difficulty` out of 10 sets how packed it is
+// with numbers and symbols; a third of the symbols are gears
+pub fn generate(seed: u64, size: usize, difficulty: usize) -> String {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut input = String::new();
+    for _ in 0..size {
+        let mut row = String::new();
+        while row.len() < size {
+            let length = rng.gen_range(1..=3u32);
+            let roll = rng.gen_range(0..80);
+            if roll < 2 * difficulty && row.len() + length as usize <= size {
+                row.push_str(
+                    &rng.gen_range(10u32.pow(length - 1)..10u32.pow(length))
+                        .to_string(),
+                );
+                // keep numbers on the same row apart
+                if row.len() < size {
+                    row.push('.');
+                }
+            } else if roll < 3 * difficulty {
+                row.push(*SYMBOLS.choose(&mut rng).unwrap());
+            } else {
+                row.push('.');
+            }
+        }
+        input.push_str(&row);
+        input.push('\n');
+    }
+    input
+}
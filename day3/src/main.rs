@@ -1,15 +1,25 @@
-fn main() {
+mod generate;
+
+fn main() -> Result<(), String> {
+    let args = std::env::args().collect::<Vec<_>>();
+    let generated = common::generated(&args, 140, 5, generate::generate)?;
+    let input = generated.as_deref().unwrap_or(INPUT);
+    if args.iter().any(|arg| arg == "--print") {
+        print!("{}", input);
+        return Ok(());
+    }
     let now = std::time::Instant::now();
-    let result = part1(INPUT);
+    let result = part1(input);
     println!("Part 1: {} [{:?}]", result, now.elapsed());
     let now = std::time::Instant::now();
-    let result = part2(INPUT);
+    let result = part2(input);
     println!("Part 2: {} [{:?}]", result, now.elapsed());
+    Ok(())
 }
 
 const INPUT: &str = include_str!("../input.txt");
 
-fn symbols_map(input: &str) -> Vec<Vec<bool>> {
+pub(crate) fn symbols_map(input: &str) -> Vec<Vec<bool>> {
     input
         .lines()
         .map(|line| {
@@ -20,7 +30,7 @@ fn symbols_map(input: &str) -> Vec<Vec<bool>> {
         .collect()
 }
 
-struct Number {
+pub(crate) struct Number {
     value: u32,
     x: usize,
     y: usize,
@@ -29,61 +39,42 @@ struct Number {
 
 impl Number {
     fn is_next_to_symbol(&self, symbols: &[Vec<bool>]) -> bool {
-        for y in (self.y.saturating_sub(1)).clamp(0, symbols.len() - 1)
-            ..=(self.y + 1).clamp(0, symbols.len() - 1)
-        {
-            for x in (self.x.saturating_sub(1)).clamp(0, symbols[y].len() - 1)
-                ..=(self.x + self.length).clamp(0, symbols[y].len() - 1)
-            {
-                if symbols[y][x] {
-                    return true;
-                }
-            }
-        }
-        false
+        let rows =
+            self.y.saturating_sub(1).min(symbols.len() - 1)..=(self.y + 1).min(symbols.len() - 1);
+        symbols[rows].iter().any(|row| {
+            let columns = self.x.saturating_sub(1).min(row.len() - 1)
+                ..=(self.x + self.length).min(row.len() - 1);
+            row[columns].iter().any(|&symbol| symbol)
+        })
     }
 }
 
-fn numbers(input: &str) -> Vec<Number> {
+pub(crate) fn numbers(input: &str) -> Vec<Number> {
     input
         .lines()
         .enumerate()
         .flat_map(|(y, line)| {
             let mut cur_num = String::new();
             let mut start_x = 0;
-            line.chars()
-                .enumerate()
-                .map(move |(x, c)| {
-                    if cur_num.is_empty() && c.is_ascii_digit() {
-                        start_x = x;
-                        cur_num.push(c);
-                        if x == line.len() - 1 {
-                            let num = cur_num.parse().unwrap();
-                            cur_num.clear();
-                            Some(Number {
-                                value: num,
-                                x: start_x,
-                                y,
-                                length: x - start_x,
-                            })
-                        } else {
-                            None
-                        }
-                    } else if c.is_ascii_digit() {
-                        cur_num.push(c);
-                        if x == line.len() - 1 {
-                            let num = cur_num.parse().unwrap();
-                            cur_num.clear();
-                            Some(Number {
-                                value: num,
-                                x: start_x,
-                                y,
-                                length: x - start_x,
-                            })
-                        } else {
-                            None
-                        }
-                    } else if !cur_num.is_empty() {
+            line.chars().enumerate().filter_map(move |(x, c)| {
+                if cur_num.is_empty() && c.is_ascii_digit() {
+                    start_x = x;
+                    cur_num.push(c);
+                    if x == line.len() - 1 {
+                        let num = cur_num.parse().unwrap();
+                        cur_num.clear();
+                        Some(Number {
+                            value: num,
+                            x: start_x,
+                            y,
+                            length: x - start_x,
+                        })
+                    } else {
+                        None
+                    }
+                } else if c.is_ascii_digit() {
+                    cur_num.push(c);
+                    if x == line.len() - 1 {
                         let num = cur_num.parse().unwrap();
                         cur_num.clear();
                         Some(Number {
@@ -95,13 +86,24 @@ fn numbers(input: &str) -> Vec<Number> {
                     } else {
                         None
                     }
-                })
-                .filter_map(|x| x)
+                } else if !cur_num.is_empty() {
+                    let num = cur_num.parse().unwrap();
+                    cur_num.clear();
+                    Some(Number {
+                        value: num,
+                        x: start_x,
+                        y,
+                        length: x - start_x,
+                    })
+                } else {
+                    None
+                }
+            })
         })
         .collect()
 }
 
-fn part1(input: &str) -> u32 {
+pub(crate) fn part1(input: &str) -> u32 {
     let symbols = symbols_map(input);
     let nums = numbers(input);
     nums.iter()
@@ -138,7 +140,7 @@ fn gear_ratio((x, y): (usize, usize), nums: &[Number]) -> Option<u32> {
     Some(first * second)
 }
 
-fn part2(input: &str) -> u32 {
+pub(crate) fn part2(input: &str) -> u32 {
     let nums = numbers(input);
     gears(input)
         .iter()
@@ -0,0 +1,59 @@
+use rand::rngs::StdRng;
+use rand::seq::{index, SliceRandom};
+use rand::{Rng, SeedableRng};
+
+// Keeps both totals well inside a u32
+const BUDGET: u64 = 1 << 31;
+
+// `size` cards with `difficulty` winning numbers each and two and a half times
+// as many held; matches never run past the last card
+pub fn generate(seed: u64, size: usize, difficulty: usize) -> String {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let winning = difficulty.clamp(1, 30);
+    let held = (winning * 5 / 2).min(99 - winning);
+    let width = size.to_string().len().max(3);
+    let mut copies = vec![1; size];
+    let mut total = size as u64;
+    let mut score = 0;
+    let mut input = String::new();
+    for card in 0..size {
+        let mut matches = rng.gen_range(0..=winning).min(size - card - 1);
+        let gained = |matches: usize| (matches as u64 * copies[card], (1 << matches) / 2);
+        while matches > 0 && {
+            let (copies, points) = gained(matches);
+            total + copies > BUDGET || score + points > BUDGET
+        } {
+            matches -= 1;
+        }
+        let (gained_copies, points) = gained(matches);
+        total += gained_copies;
+        score += points;
+        for won in card + 1..=card + matches {
+            copies[won] += copies[card];
+        }
+
+        // the first `winning` numbers win, the held ones take `matches` of them
+        let mut numbers = index::sample(&mut rng, 99, winning + held - matches)
+            .into_iter()
+            .map(|n| n + 1)
+            .collect::<Vec<_>>();
+        let mut held_numbers = numbers.split_off(winning);
+        held_numbers.extend_from_slice(&numbers[..matches]);
+        numbers.shuffle(&mut rng);
+        held_numbers.shuffle(&mut rng);
+        let side = |numbers: &[usize]| {
+            numbers
+                .iter()
+                .map(|n| format!("{:>2}", n))
+                .collect::<Vec<_>>()
+                .join(" ")
+        };
+        input.push_str(&format!(
+            "Card {:>width$}: {} | {}\n",
+            card + 1,
+            side(&numbers),
+            side(&held_numbers),
+        ));
+    }
+    input
+}
@@ -1,83 +1,245 @@
 use nom::{
-    character::complete::{u32, space0},
+    bytes::complete::tag,
+    character::complete::{space0, space1, u32},
+    combinator::{all_consuming, map},
     multi::many1,
-    IResult, sequence::delimited,
+    sequence::{delimited, preceded, tuple},
+    IResult,
 };
-use std::collections::HashSet;
 
-fn main() {
+mod generate;
+
+fn main() -> Result<(), String> {
+    let args = std::env::args().collect::<Vec<_>>();
+    let generated = common::generated(&args, 200, 10, generate::generate)?;
+    let input = generated.as_deref().unwrap_or(INPUT);
+    if args.iter().any(|arg| arg == "--print") {
+        print!("{}", input);
+        return Ok(());
+    }
+    if args.iter().any(|arg| arg == "--trace") {
+        for card in trace(&parse(input)?)? {
+            println!("{}", card);
+        }
+        return Ok(());
+    }
     let now = std::time::Instant::now();
-    let result = part1(INPUT);
+    let result = part1(input)?;
     println!("Part 1: {} [{:?}]", result, now.elapsed());
     let now = std::time::Instant::now();
-    let result = part2(INPUT);
+    let result = part2(input)?;
     println!("Part 2: {} [{:?}]", result, now.elapsed());
+    Ok(())
 }
 
 const INPUT: &str = include_str!("../input.txt");
 
+#[derive(Debug)]
+pub(crate) struct Scratchcard {
+    id: u32,
+    winning: Vec<u32>,
+    held: Vec<u32>,
+}
+
+impl Scratchcard {
+    fn matches(&self) -> usize {
+        self.held
+            .iter()
+            .filter(|n| self.winning.contains(n))
+            .count()
+    }
+
+    fn score(&self) -> Result<u32, String> {
+        match self.matches() {
+            0 => Ok(0),
+            n => 1u32
+                .checked_shl(n as u32 - 1)
+                .ok_or_else(|| format!("card {}: {} matches overflow the score", self.id, n)),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct CardTrace {
+    id: u32,
+    copies: u32,
+    score: u32,
+    // (card id, copies won from it), in card order
+    sources: Vec<(u32, u32)>,
+}
+
+impl std::fmt::Display for CardTrace {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "Card {}: {} copies, score {}",
+            self.id, self.copies, self.score
+        )?;
+        if !self.sources.is_empty() {
+            let sources = self
+                .sources
+                .iter()
+                .map(|(id, copies)| format!("card {} x{}", id, copies))
+                .collect::<Vec<_>>();
+            write!(f, " <- {}", sources.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+fn trace(cards: &[Scratchcard]) -> Result<Vec<CardTrace>, String> {
+    let mut traces = cards
+        .iter()
+        .map(|card| {
+            Ok(CardTrace {
+                id: card.id,
+                copies: 1,
+                score: card.score()?,
+                sources: Vec::new(),
+            })
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+    for (i, card) in cards.iter().enumerate() {
+        let copies = traces[i].copies;
+        for won in traces.iter_mut().skip(i + 1).take(card.matches()) {
+            won.copies = won
+                .copies
+                .checked_add(copies)
+                .ok_or_else(|| format!("card {}: copies overflow", won.id))?;
+            won.sources.push((card.id, copies));
+        }
+    }
+    Ok(traces)
+}
+
 fn parse_numbers(input: &str) -> IResult<&str, Vec<u32>> {
-    many1(
-        delimited(space0, u32, space0)
+    many1(delimited(space0, u32, space0))(input)
+}
+
+fn scratchcard(input: &str) -> IResult<&str, Scratchcard> {
+    map(
+        tuple((
+            delimited(tuple((tag("Card"), space1)), u32, tag(":")),
+            parse_numbers,
+            preceded(tag("|"), parse_numbers),
+        )),
+        |(id, winning, held)| Scratchcard { id, winning, held },
     )(input)
 }
 
-fn part1(input: &str) -> u32 {
-    input
-        .lines()
-        .map(|line| {
-            let mut thingy = line
-                .split(": ")
-                .skip(1)
-                .next()
-                .unwrap()
-                .split(" | ")
-                .map(|s| -> HashSet<u32> { HashSet::from_iter(parse_numbers(s).unwrap().1) });
-            thingy
-                .next()
-                .unwrap()
-                .intersection(&thingy.next().unwrap())
-                .fold(0, |acc, _| {
-                    if acc == 0 {
-                        1
-                    } else {
-                        acc * 2
-                    }
-                })
-        })
-        .sum()
+fn check_duplicates(numbers: &[u32], side: &str, line: usize) -> Result<(), String> {
+    for (i, n) in numbers.iter().enumerate() {
+        if numbers[..i].contains(n) {
+            return Err(format!(
+                "line {}: duplicate number {} in {} numbers",
+                line, n, side
+            ));
+        }
+    }
+    Ok(())
 }
 
-fn part2(input: &str) -> u32 {
-    let cards = input
-        .lines()
-        .map(|line| {
-            let mut thingy = line
-                .split(": ")
-                .skip(1)
-                .next()
-                .unwrap()
-                .split(" | ")
-                .map(|s| -> HashSet<u32> { HashSet::from_iter(parse_numbers(s).unwrap().1) });
-            (thingy.next().unwrap(), thingy.next().unwrap())
-        })
-        .collect::<Vec<_>>();
-    let mut count = 0;
-    let mut cache = vec![0; cards.len()];
-    for i in 0..cards.len() {
-        count += part2_helper(&cards, i, &mut cache) + 1;
+pub(crate) fn parse(input: &str) -> Result<Vec<Scratchcard>, String> {
+    let mut cards = Vec::new();
+    for (i, line) in input.lines().enumerate() {
+        let line_no = i + 1;
+        let card = all_consuming(scratchcard)(line)
+            .map_err(|e| format!("line {}: invalid card: {}", line_no, e))?
+            .1;
+        let expected = cards.len() as u32 + 1;
+        if card.id != expected {
+            return Err(format!(
+                "line {}: expected card {}, found card {}",
+                line_no, expected, card.id
+            ));
+        }
+        check_duplicates(&card.winning, "winning", line_no)?;
+        check_duplicates(&card.held, "held", line_no)?;
+        cards.push(card);
     }
-    count
+    Ok(cards)
+}
+
+pub(crate) fn part1(input: &str) -> Result<u32, String> {
+    parse(input)?.iter().try_fold(0u32, |total, card| {
+        total
+            .checked_add(card.score()?)
+            .ok_or_else(|| "total score overflows".to_string())
+    })
 }
 
-fn part2_helper(cards: &Vec<(HashSet<u32>, HashSet<u32>)>, id: usize, cache: &mut Vec<u32>) -> u32 {
-    if cache[id] != 0 {
-        return cache[id];
+// Cards won past the last one don't exist, `trace` stops at the end
+pub(crate) fn part2(input: &str) -> Result<u32, String> {
+    trace(&parse(input)?)?
+        .iter()
+        .try_fold(0u32, |total, card| total.checked_add(card.copies))
+        .ok_or_else(|| "total copies overflow".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11
+";
+
+    #[test]
+    fn example() {
+        assert_eq!(part1(EXAMPLE), Ok(13));
+        assert_eq!(part2(EXAMPLE), Ok(30));
+    }
+
+    #[test]
+    fn trace_follows_copies() {
+        let traces = trace(&parse(EXAMPLE).unwrap()).unwrap();
+        let copies = traces.iter().map(|card| card.copies).collect::<Vec<_>>();
+        assert_eq!(copies, [1, 2, 4, 8, 14, 1]);
+        assert_eq!(traces[3].sources, [(1, 1), (2, 2), (3, 4)]);
+        assert_eq!(
+            traces[3].to_string(),
+            "Card 4: 8 copies, score 1 <- card 1 x1, card 2 x2, card 3 x4"
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_cards() {
+        let skipped = EXAMPLE.replace("Card 2:", "Card 3:");
+        assert_eq!(
+            part1(&skipped),
+            Err("line 2: expected card 2, found card 3".to_string())
+        );
+        let duplicate = EXAMPLE.replace("| 83 86  6", "| 83 86 83");
+        assert_eq!(
+            part1(&duplicate),
+            Err("line 1: duplicate number 83 in held numbers".to_string())
+        );
+        let garbled = EXAMPLE.replace("Card 5: 87", "Card 5: x87");
+        assert!(part1(&garbled)
+            .unwrap_err()
+            .starts_with("line 5: invalid card"));
     }
-    let mut count = cards[id].0.intersection(&cards[id].1).count() as u32;
-    for i in 1..=count {
-        count += part2_helper(cards, id + i as usize, cache);
+
+    #[test]
+    fn reports_overflow() {
+        let numbers = (1..=33)
+            .map(|n| n.to_string())
+            .collect::<Vec<_>>()
+            .join(" ");
+        let card = format!("Card 1: {} | {}", numbers, numbers);
+        assert_eq!(
+            part1(&card),
+            Err("card 1: 33 matches overflow the score".to_string())
+        );
+        // every card doubles the copies of the next two
+        let cards = (1..=80)
+            .map(|id| format!("Card {}: 1 2 | 1 2\n", id))
+            .collect::<String>();
+        assert!(part2(&cards).unwrap_err().ends_with("copies overflow"));
     }
-    cache[id] = count;
-    count
 }
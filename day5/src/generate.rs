@@ -0,0 +1,70 @@
+use rand::rngs::StdRng;
+use rand::seq::{index, SliceRandom};
+use rand::{Rng, SeedableRng};
+
+const CATEGORIES: [&str; 8] = [
+    "seed",
+    "soil",
+    "fertilizer",
+    "water",
+    "light",
+    "temperature",
+    "humidity",
+    "location",
+];
+
+// Maps of `size` entries that shuffle contiguous pieces of 0..2^32 around like
+// the real ones, and `difficulty` seed ranges of up to a sixteenth of that each,
+// so they overlap one another more and more and cut across many entries. Each
+// map also loses up to `difficulty` pieces, leaving holes values pass straight
+// through, and gains `difficulty` stray entries overlapping the others.
+pub fn generate(seed: u64, size: usize, difficulty: usize) -> String {
+    generate_within(seed, size, difficulty, 1 << 32)
+}
+
+// The same over 0..`span`, which needs room for `size` entries
+pub fn generate_within(seed: u64, size: usize, difficulty: usize, span: i64) -> String {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut input = String::from("seeds:");
+    for _ in 0..difficulty.max(1) {
+        let start = rng.gen_range(0..span);
+        let len = rng.gen_range(1..=(span / 16).max(1));
+        input += &format!(" {} {}", start, len);
+    }
+    input += "\n";
+    for pair in CATEGORIES.windows(2) {
+        input += &format!("\n{}-to-{} map:\n", pair[0], pair[1]);
+        let mut cuts = index::sample(&mut rng, span as usize - 1, size.max(1) - 1)
+            .into_iter()
+            .map(|cut| cut as i64 + 1)
+            .collect::<Vec<_>>();
+        cuts.extend([0, span]);
+        cuts.sort_unstable();
+        let mut pieces = cuts
+            .windows(2)
+            .map(|cut| cut[0]..cut[1])
+            .collect::<Vec<_>>();
+        pieces.shuffle(&mut rng);
+        let mut dest = 0;
+        let mut entries = pieces
+            .iter()
+            .map(|piece| {
+                let entry = (dest, piece.start, piece.end - piece.start);
+                dest += entry.2;
+                entry
+            })
+            .collect::<Vec<_>>();
+        entries.shuffle(&mut rng);
+        entries.truncate(entries.len() - rng.gen_range(0..=difficulty.min(entries.len() - 1)));
+        let stray = (span / size.max(1) as i64 * 2).max(1);
+        for _ in 0..difficulty {
+            let len = rng.gen_range(1..=stray);
+            entries.push((rng.gen_range(0..span), rng.gen_range(0..span), len));
+        }
+        entries.shuffle(&mut rng);
+        for (dest, src, len) in entries {
+            input += &format!("{} {} {}\n", dest, src, len);
+        }
+    }
+    input
+}
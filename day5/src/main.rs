@@ -1,104 +1,649 @@
-use std::str::Lines;
+use std::collections::HashSet;
+use std::ops::Range;
 
-use rangemap::RangeMap;
+use rangemap::{RangeMap, RangeSet};
 
-fn main() {
+mod generate;
+#[cfg(test)]
+mod oracle;
+
+fn main() -> Result<(), String> {
+    let args = std::env::args().collect::<Vec<_>>();
+    let generated = common::generated(&args, 40, 10, generate::generate)?;
+    let input = generated.as_deref().unwrap_or(INPUT);
+    if args.iter().any(|arg| arg == "--print") {
+        print!("{}", input);
+        return Ok(());
+    }
+    if args
+        .iter()
+        .any(|arg| arg == "--validate" || arg == "--strict")
+    {
+        let issues = almanac(input)?.validate();
+        for issue in &issues {
+            eprintln!("{}", issue);
+        }
+        if args.iter().any(|arg| arg == "--strict") {
+            strict(&issues)?;
+        }
+        if args.iter().any(|arg| arg == "--validate") {
+            return Ok(());
+        }
+    } else {
+        // Answers silently change without these entries, so at least say so
+        for skipped in almanac(input)?.skipped() {
+            eprintln!("warning: {}", skipped);
+        }
+    }
+    if let Some(i) = args.iter().position(|arg| arg == "--convert") {
+        let usage = "usage: --convert <from> <to> <value>";
+        let from = args.get(i + 1).ok_or(usage)?;
+        let to = args.get(i + 2).ok_or(usage)?;
+        let value = args.get(i + 3).ok_or(usage)?;
+        let value = value
+            .parse()
+            .map_err(|_| format!("invalid value {:?}", value))?;
+        println!("{}", almanac(input)?.convert(value, from, to)?);
+        return Ok(());
+    }
+    if let Some(i) = args.iter().position(|arg| arg == "--preimage") {
+        let usage = "usage: --preimage <from> <to> <start> <end>";
+        let from = args.get(i + 1).ok_or(usage)?;
+        let to = args.get(i + 2).ok_or(usage)?;
+        let start = args.get(i + 3).ok_or(usage)?;
+        let start = start
+            .parse()
+            .map_err(|_| format!("invalid start {:?}", start))?;
+        let end = args.get(i + 4).ok_or(usage)?;
+        let end = end.parse().map_err(|_| format!("invalid end {:?}", end))?;
+        for range in almanac(input)?.preimage(&(start..end), from, to)? {
+            println!("{}..{}", range.start, range.end);
+        }
+        return Ok(());
+    }
+    if args.iter().any(|arg| arg == "--check") {
+        let forward = part2(input)?;
+        let inverse = part2_inverse(input)?;
+        println!("Forward: {}, inverse: {}", forward, inverse);
+        if forward != inverse {
+            return Err("forward and inverse answers differ".to_string());
+        }
+        return Ok(());
+    }
+    if args.iter().any(|arg| arg == "--fragments") {
+        for (name, input) in [
+            ("input", input.to_string()),
+            ("adversarial", adversarial_almanac(7, 200)),
+        ] {
+            let almanac = almanac(&input)?;
+            let path = almanac.path("seed", "location")?;
+            let (_, raw) = propagate_raw(almanac.seed_ranges(), &path);
+            let seeds = almanac.seed_ranges().into_iter().collect();
+            let (_, coalesced) = propagate(seeds, &path);
+            println!("{}:", name);
+            for (map, (raw, coalesced)) in path.iter().zip(raw.into_iter().zip(coalesced)) {
+                println!(
+                    "  {}-to-{}: {} fragments, {} after coalescing",
+                    map.source, map.destination, raw, coalesced
+                );
+            }
+        }
+        return Ok(());
+    }
+    if args.iter().any(|arg| arg == "--composed") {
+        let almanac = almanac(input)?;
+        let composed = almanac.composed("seed", "location")?;
+        for (range, offset) in composed.iter() {
+            println!("{}..{} {:+}", range.start, range.end, offset);
+        }
+        let lowest = almanac
+            .seed_ranges()
+            .iter()
+            .flat_map(|seeds| {
+                composed
+                    .overlapping(seeds)
+                    .map(|(range, offset)| range.start.max(seeds.start) + offset)
+            })
+            .min();
+        println!("Lowest location: {:?}", lowest);
+        return Ok(());
+    }
     let now = std::time::Instant::now();
-    let result = part1(INPUT);
+    let result = part1(input)?;
     println!("Part 1: {} [{:?}]", result, now.elapsed());
     let now = std::time::Instant::now();
-    let result = part2(INPUT);
+    let result = part2(input)?;
     println!("Part 2: {} [{:?}]", result, now.elapsed());
+    Ok(())
 }
 
 const INPUT: &str = include_str!("../input.txt");
 
-fn ranges(mut lines: Lines) -> Vec<RangeMap<i64, i64>> {
-    let mut ranges = Vec::with_capacity(7);
-    lines.next();
-    for _ in 0..7 {
-        lines.next();
-        let mut range = RangeMap::new();
-        while let Some(line) = lines.next() {
-            if line.is_empty() {
-                break;
+const DOMAIN: Range<i64> = i64::MIN..i64::MAX;
+
+// Values stay within +/- LIMIT so offsets and their sums along a chain fit in an i64
+const LIMIT: i64 = 1 << 62;
+
+#[derive(Debug)]
+struct Entry {
+    dest: i64,
+    src: i64,
+    len: i64,
+    line: usize,
+}
+
+impl Entry {
+    fn source(&self) -> Range<i64> {
+        self.src..self.src.saturating_add(self.len)
+    }
+
+    fn destination(&self) -> Range<i64> {
+        self.dest..self.dest.saturating_add(self.len)
+    }
+
+    fn in_limits(&self) -> bool {
+        [self.source(), self.destination()]
+            .iter()
+            .all(|range| range.start >= -LIMIT && range.end <= LIMIT)
+    }
+
+    // Why `almanac` leaves this entry out of the map, if it does
+    fn skipped(&self) -> Option<String> {
+        if self.len <= 0 {
+            Some(format!("has length {}", self.len))
+        } else if !self.in_limits() {
+            Some("risks overflow".to_string())
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Debug)]
+struct CategoryMap<'a> {
+    source: &'a str,
+    destination: &'a str,
+    entries: Vec<Entry>,
+    ranges: RangeMap<i64, i64>,
+}
+
+#[derive(Debug)]
+pub(crate) struct Almanac<'a> {
+    seeds: Vec<i64>,
+    // part 2 reads the seeds as (start, length) pairs
+    seed_ranges: Vec<Range<i64>>,
+    maps: Vec<CategoryMap<'a>>,
+}
+
+fn parse_number(s: Option<&str>, line: usize) -> Result<i64, String> {
+    let s = s.ok_or_else(|| format!("line {}: missing number", line))?;
+    s.parse()
+        .map_err(|_| format!("line {}: invalid number {:?}", line, s))
+}
+
+fn strict(issues: &[String]) -> Result<(), String> {
+    match issues.len() {
+        0 => Ok(()),
+        n => Err(format!("refusing to solve, found {} issues", n)),
+    }
+}
+
+pub(crate) fn almanac(input: &str) -> Result<Almanac<'_>, String> {
+    let mut lines = input.lines().enumerate().map(|(i, line)| (i + 1, line));
+    let seeds = match lines.next() {
+        Some((_, line)) if line.starts_with("seeds:") => line["seeds:".len()..]
+            .split_whitespace()
+            .map(|s| parse_number(Some(s), 1))
+            .collect::<Result<Vec<_>, _>>()?,
+        _ => return Err("line 1: expected seeds".to_string()),
+    };
+    if seeds.len() % 2 != 0 {
+        return Err(format!(
+            "line 1: {} seed numbers, expected (start, length) pairs",
+            seeds.len()
+        ));
+    }
+    let seed_ranges = seeds
+        .chunks(2)
+        .map(|pair| {
+            let len = pair[1];
+            if len <= 0 {
+                return Err(format!("line 1: seed range {} has length {}", pair[0], len));
+            }
+            let end = pair[0]
+                .checked_add(len)
+                .ok_or_else(|| format!("line 1: seed range {} + {} overflows", pair[0], len))?;
+            Ok(pair[0]..end)
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+    let mut maps: Vec<CategoryMap> = Vec::new();
+    for (line_no, line) in lines {
+        if line.is_empty() {
+            continue;
+        }
+        if let Some(header) = line.strip_suffix(" map:") {
+            let (source, destination) = header
+                .split_once("-to-")
+                .ok_or_else(|| format!("line {}: invalid map header {:?}", line_no, line))?;
+            if maps
+                .iter()
+                .any(|map| map.source == source && map.destination == destination)
+            {
+                return Err(format!(
+                    "line {}: duplicate {}-to-{} map",
+                    line_no, source, destination
+                ));
             }
-            let mut iter = line.split_whitespace();
-            let dest_start = iter.next().unwrap().parse::<i64>().unwrap();
-            let src_start = iter.next().unwrap().parse::<i64>().unwrap();
-            let len = iter.next().unwrap().parse::<i64>().unwrap();
-            let offset = dest_start - src_start;
-            range.insert(src_start..src_start + len, offset);
+            maps.push(CategoryMap {
+                source,
+                destination,
+                entries: Vec::new(),
+                ranges: RangeMap::new(),
+            });
+            continue;
+        }
+        let map = maps
+            .last_mut()
+            .ok_or_else(|| format!("line {}: entry outside of a map", line_no))?;
+        let mut iter = line.split_whitespace();
+        let dest = parse_number(iter.next(), line_no)?;
+        let src = parse_number(iter.next(), line_no)?;
+        let len = parse_number(iter.next(), line_no)?;
+        if iter.next().is_some() {
+            return Err(format!("line {}: trailing data in entry", line_no));
         }
-        ranges.push(range);
+        let entry = Entry {
+            dest,
+            src,
+            len,
+            line: line_no,
+        };
+        // Anything skipped here is reported by `Almanac::validate`, and so are
+        // overlaps, where the entry listed last wins
+        if entry.skipped().is_none() {
+            map.ranges.insert(entry.source(), dest - src);
+        }
+        map.entries.push(entry);
     }
-    ranges
+    let almanac = Almanac {
+        seeds,
+        seed_ranges,
+        maps,
+    };
+    almanac.check_acyclic()?;
+    Ok(almanac)
 }
 
-fn part1(input: &str) -> i64 {
-    let mut lines = input.lines();
-    let seeds = lines
-        .next()
-        .unwrap()
-        .split(": ")
-        .nth(1)
-        .unwrap()
-        .split_whitespace()
-        .map(|s| s.parse().unwrap())
-        .collect::<Vec<i64>>();
-    *ranges(lines)
+impl<'a> Almanac<'a> {
+    fn validate(&self) -> Vec<String> {
+        let mut issues = Vec::new();
+        for map in &self.maps {
+            let name = format!("{}-to-{}", map.source, map.destination);
+            for (i, entry) in map.entries.iter().enumerate() {
+                if let Some(reason) = entry.skipped() {
+                    issues.push(format!("line {}: {} entry {}", entry.line, name, reason));
+                    continue;
+                }
+                for other in map.entries[..i].iter().filter(|other| other.len > 0) {
+                    let overlaps =
+                        |a: Range<i64>, b: Range<i64>| a.start < b.end && b.start < a.end;
+                    if overlaps(entry.source(), other.source()) {
+                        issues.push(format!(
+                            "line {}: {} source range overlaps line {}",
+                            entry.line, name, other.line
+                        ));
+                    }
+                    if overlaps(entry.destination(), other.destination()) {
+                        issues.push(format!(
+                            "line {}: {} destination range overlaps line {}",
+                            entry.line, name, other.line
+                        ));
+                    }
+                }
+            }
+        }
+        issues
+    }
+
+    fn skipped(&self) -> Vec<String> {
+        self.maps
+            .iter()
+            .flat_map(|map| {
+                map.entries.iter().filter_map(move |entry| {
+                    entry.skipped().map(|reason| {
+                        format!(
+                            "line {}: {}-to-{} entry {}, skipped",
+                            entry.line, map.source, map.destination, reason
+                        )
+                    })
+                })
+            })
+            .collect()
+    }
+
+    fn check_acyclic(&self) -> Result<(), String> {
+        // colour: 0 = unvisited, 1 = on the stack, 2 = done
+        fn visit<'a>(
+            almanac: &Almanac<'a>,
+            category: &'a str,
+            colours: &mut Vec<(&'a str, u8)>,
+        ) -> Result<(), String> {
+            match colours.iter().find(|(c, _)| *c == category) {
+                Some((_, 1)) => return Err(format!("category cycle through {}", category)),
+                Some((_, 2)) => return Ok(()),
+                _ => colours.push((category, 1)),
+            }
+            for map in almanac.maps.iter().filter(|map| map.source == category) {
+                visit(almanac, map.destination, colours)?;
+            }
+            colours.iter_mut().find(|(c, _)| *c == category).unwrap().1 = 2;
+            Ok(())
+        }
+
+        let mut colours = Vec::new();
+        for map in &self.maps {
+            visit(self, map.source, &mut colours)?;
+        }
+        Ok(())
+    }
+
+    fn path(&self, from: &str, to: &str) -> Result<Vec<&CategoryMap<'a>>, String> {
+        // `dead_ends` keeps the search linear, the maps can form a DAG with
+        // exponentially many paths
+        fn search<'m, 'a: 'f, 'f>(
+            maps: &'m [CategoryMap<'a>],
+            from: &'f str,
+            to: &str,
+            path: &mut Vec<&'m CategoryMap<'a>>,
+            dead_ends: &mut HashSet<&'f str>,
+        ) -> bool {
+            if from == to {
+                return true;
+            }
+            if dead_ends.contains(from) {
+                return false;
+            }
+            for map in maps.iter().filter(|map| map.source == from) {
+                path.push(map);
+                if search(maps, map.destination, to, path, dead_ends) {
+                    return true;
+                }
+                path.pop();
+            }
+            dead_ends.insert(from);
+            false
+        }
+
+        let mut path = Vec::new();
+        if search(&self.maps, from, to, &mut path, &mut HashSet::new()) {
+            Ok(path)
+        } else {
+            Err(format!("no path from {} to {}", from, to))
+        }
+    }
+
+    fn convert(&self, value: i64, from: &str, to: &str) -> Result<i64, String> {
+        Ok(follow(&self.path(from, to)?, value))
+    }
+
+    fn composed(&self, from: &str, to: &str) -> Result<RangeMap<i64, i64>, String> {
+        Ok(self
+            .path(from, to)?
+            .iter()
+            .fold(filled(&RangeMap::new()), |acc, map| {
+                compose(&acc, &filled(&map.ranges))
+            }))
+    }
+
+    // All values of `from` that land in `target` once converted to `to`
+    fn preimage(
+        &self,
+        target: &Range<i64>,
+        from: &str,
+        to: &str,
+    ) -> Result<Vec<Range<i64>>, String> {
+        Ok(preimage(&self.composed(from, to)?, target))
+    }
+
+    // never empty, `RangeSet` refuses those
+    fn seed_ranges(&self) -> Vec<Range<i64>> {
+        self.seed_ranges.clone()
+    }
+}
+
+fn filled(map: &RangeMap<i64, i64>) -> RangeMap<i64, i64> {
+    let mut map = map.clone();
+    for gap in map.gaps(&DOMAIN).collect::<Vec<_>>() {
+        map.insert(gap, 0);
+    }
+    map
+}
+
+// Both maps must cover the whole domain, see `filled`
+fn compose(first: &RangeMap<i64, i64>, second: &RangeMap<i64, i64>) -> RangeMap<i64, i64> {
+    let mut composed = RangeMap::new();
+    for (range, a) in first.iter() {
+        let image = range.start + a..range.end + a;
+        for (next, b) in second.overlapping(&image) {
+            let start = image.start.max(next.start) - a;
+            let end = image.end.min(next.end) - a;
+            composed.insert(start..end, a + b);
+        }
+    }
+    composed
+}
+
+fn follow(path: &[&CategoryMap], value: i64) -> i64 {
+    path.iter().fold(value, |value, map| {
+        value + map.ranges.get(&value).unwrap_or(&0)
+    })
+}
+
+// `map` must cover the whole domain, see `filled`
+fn preimage(map: &RangeMap<i64, i64>, target: &Range<i64>) -> Vec<Range<i64>> {
+    let mut ranges = map
         .iter()
-        .fold(seeds, |acc, range| {
-            acc.iter()
-                .map(|&seed| seed + range.get(&seed).unwrap_or(&0))
-                .collect::<Vec<_>>()
+        .filter_map(|(range, offset)| {
+            let start = (range.start + offset).max(target.start);
+            let end = (range.end + offset).min(target.end);
+            (start < end).then(|| start - offset..end - offset)
         })
+        .collect::<Vec<_>>();
+    ranges.sort_by_key(|range| range.start);
+    ranges
+}
+
+pub(crate) fn part1(input: &str) -> Result<i64, String> {
+    let almanac = almanac(input)?;
+    let path = almanac.path("seed", "location")?;
+    almanac
+        .seeds
         .iter()
+        .map(|&seed| follow(&path, seed))
         .min()
-        .unwrap()
+        .ok_or_else(|| "no seeds".to_string())
+}
+
+// Images of `rangee` under `map`, one fragment for every piece or gap it crosses
+fn step(rangee: &Range<i64>, map: &RangeMap<i64, i64>) -> Vec<Range<i64>> {
+    let overlapping = map.overlapping(rangee).map(|(range, offset)| {
+        range.start.max(rangee.start) + offset..range.end.min(rangee.end) + offset
+    });
+    overlapping.chain(map.gaps(rangee)).collect()
 }
 
-fn part2(input: &str) -> i64 {
-    let mut lines = input.lines();
-    let seeds = lines
+// Pushes `seeds` through each map in turn, coalescing after every step, and
+// returns the resulting set along with the fragment count of every step
+fn propagate(seeds: RangeSet<i64>, maps: &[&CategoryMap]) -> (RangeSet<i64>, Vec<usize>) {
+    let mut counts = Vec::with_capacity(maps.len());
+    let mut last = seeds;
+    for map in maps {
+        last = last
+            .iter()
+            .flat_map(|range| step(range, &map.ranges))
+            .collect();
+        counts.push(last.len());
+    }
+    (last, counts)
+}
+
+// Same as `propagate` but never coalesces, to show what that saves
+fn propagate_raw(seeds: Vec<Range<i64>>, maps: &[&CategoryMap]) -> (Vec<Range<i64>>, Vec<usize>) {
+    let mut counts = Vec::with_capacity(maps.len());
+    let mut last = seeds;
+    for map in maps {
+        last = last
+            .iter()
+            .flat_map(|range| step(range, &map.ranges))
+            .collect();
+        counts.push(last.len());
+    }
+    (last, counts)
+}
+
+pub(crate) fn part2(input: &str) -> Result<i64, String> {
+    let almanac = almanac(input)?;
+    let seeds = almanac.seed_ranges().into_iter().collect();
+    let (locations, _) = propagate(seeds, &almanac.path("seed", "location")?);
+    locations
+        .iter()
         .next()
-        .unwrap()
-        .split(": ")
-        .nth(1)
-        .unwrap()
-        .split_whitespace()
-        .map(|s| s.parse().unwrap())
-        .fold((Vec::new(), None), |(mut acc, prev), seed| {
-            if let Some(prev) = prev {
-                acc.push(prev..prev+seed);
-                (acc, None)
-            } else {
-                (acc, Some(seed))
-            }
-        })
-        .0
+        .map(|range| range.start)
+        .ok_or_else(|| "no seeds".to_string())
+}
+
+// Many short overlapping seed ranges pushed through maps made of many small
+// overlapping entries that scatter neighbouring values, so fragments multiply
+// at every step
+fn adversarial_almanac(maps: usize, entries: i64) -> String {
+    let category = |k: usize| match k {
+        0 => "seed".to_string(),
+        k if k == maps => "location".to_string(),
+        k => format!("category{}", k),
+    };
+    let mut almanac = String::from("seeds:");
+    for i in 0..entries {
+        almanac += &format!(" {} {}", i * 5, 7);
+    }
+    almanac += "\n";
+    for k in 0..maps {
+        almanac += &format!("\n{}-to-{} map:\n", category(k), category(k + 1));
+        for j in 0..entries {
+            let src = j * 3;
+            let dest = (entries * 3 - src) * (k as i64 + 2);
+            almanac += &format!("{} {} {}\n", dest, src, 5);
+        }
+    }
+    almanac
+}
+
+// Walks the composed map's location pieces upwards and stops at the first one
+// that can't beat the best location found so far
+pub(crate) fn part2_inverse(input: &str) -> Result<i64, String> {
+    let almanac = almanac(input)?;
+    let seeds = almanac.seed_ranges();
+    let composed = almanac.composed("seed", "location")?;
+    let mut pieces = composed
         .iter()
-        .map(|range| range.clone())
+        .map(|(range, &offset)| (range.start + offset..range.end + offset, offset))
         .collect::<Vec<_>>();
-    let ranges = ranges(lines);
-    let mut last = seeds;
-    for map in ranges {
-        let qwq = last.iter();
-        let uwu = qwq.clone()
-            .flat_map(|rangee| {
-                map.overlapping(rangee).map(|(range, offset)| {
-                    range.start.max(rangee.start)+offset..range.end.min(rangee.end)+offset
-                })
-            });
-        let owo = qwq
-            .flat_map(|range| {
-                map.gaps(&range)
-            });
-        last = uwu.chain(owo)
-            .collect::<Vec<_>>();
+    pieces.sort_by_key(|(locations, _)| locations.start);
+    let mut best: Option<i64> = None;
+    for (locations, offset) in pieces {
+        if best.is_some_and(|best| locations.start >= best) {
+            break;
+        }
+        let source = locations.start - offset..locations.end - offset;
+        for seeds in &seeds {
+            let start = source.start.max(seeds.start);
+            if start < source.end.min(seeds.end) {
+                let location = start + offset;
+                best = Some(best.map_or(location, |best| best.min(location)));
+            }
+        }
+    }
+    best.ok_or_else(|| "no seeds".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FLAWED: &str = "\
+seeds: 79 14
+
+seed-to-location map:
+0 10 5
+100 12 5
+2 50 5
+7 60 0
+0 4611686018427387904 1
+";
+
+    #[test]
+    fn validate_reports_every_issue() {
+        let almanac = almanac(FLAWED).unwrap();
+        assert_eq!(
+            almanac.validate(),
+            [
+                "line 5: seed-to-location source range overlaps line 4",
+                "line 6: seed-to-location destination range overlaps line 4",
+                "line 7: seed-to-location entry has length 0",
+                "line 8: seed-to-location entry risks overflow",
+            ]
+        );
+        assert_eq!(
+            almanac.skipped(),
+            [
+                "line 7: seed-to-location entry has length 0, skipped",
+                "line 8: seed-to-location entry risks overflow, skipped",
+            ]
+        );
+    }
+
+    #[test]
+    fn path_search_skips_dead_ends() {
+        // two categories per layer, each mapping to both of the next layer
+        let mut input = String::from("seeds: 1 1\n");
+        for layer in 0..40 {
+            for (from, to) in [("a", "a"), ("a", "b"), ("b", "a"), ("b", "b")] {
+                input += &format!("\nc{}{}-to-c{}{} map:\n0 0 1\n", layer, from, layer + 1, to);
+            }
+        }
+        let almanac = almanac(&input).unwrap();
+        assert_eq!(almanac.path("c0a", "c40b").unwrap().len(), 40);
+        assert_eq!(
+            almanac.path("c0a", "location").unwrap_err(),
+            "no path from c0a to location"
+        );
+    }
+
+    #[test]
+    fn strict_refuses_issues() {
+        assert_eq!(
+            strict(&almanac(FLAWED).unwrap().validate()),
+            Err("refusing to solve, found 4 issues".to_string())
+        );
+        assert_eq!(strict(&almanac(INPUT).unwrap().validate()), Ok(()));
+    }
+
+    #[test]
+    fn coalescing_keeps_the_answer_and_bounds_fragments() {
+        let input = adversarial_almanac(7, 50);
+        let almanac = almanac(&input).unwrap();
+        let path = almanac.path("seed", "location").unwrap();
+        let (raw, raw_counts) = propagate_raw(almanac.seed_ranges(), &path);
+        let seeds = almanac.seed_ranges().into_iter().collect::<RangeSet<_>>();
+        let mut bound = seeds.len();
+        let (coalesced, counts) = propagate(seeds, &path);
+        let lowest = raw.iter().map(|range| range.start).min();
+        assert_eq!(coalesced.iter().next().map(|range| range.start), lowest);
+        assert_eq!(part2(&input).ok(), lowest);
+        assert_eq!(part2_inverse(&input).ok(), lowest);
+        for ((map, count), raw_count) in path.iter().zip(counts).zip(raw_counts) {
+            // every entry adds at most two breakpoints, each splitting one range
+            bound += 2 * map.entries.len();
+            assert!(count <= bound.min(raw_count));
+        }
     }
-    last.iter()
-        .map(|range| range.start)
-        .min()
-        .unwrap()
 }
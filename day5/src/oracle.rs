@@ -0,0 +1,79 @@
+use proptest::prelude::*;
+use proptest::test_runner::TestCaseError;
+
+use super::generate;
+
+// Slow but obviously right: seeds and maps read straight off the text, and
+// every single seed walked through the maps one entry at a time, where entries
+// overlap the last one listed wins like it does in `almanac`
+fn parse(input: &str) -> (Vec<i64>, Vec<Vec<[i64; 3]>>) {
+    let mut sections = input.split("\n\n");
+    let seeds = sections
+        .next()
+        .unwrap()
+        .trim_start_matches("seeds:")
+        .split_whitespace()
+        .map(|n| n.parse().unwrap())
+        .collect();
+    let maps = sections
+        .map(|section| {
+            section
+                .lines()
+                .skip(1)
+                .map(|line| {
+                    let mut numbers = line.split_whitespace().map(|n| n.parse().unwrap());
+                    [(); 3].map(|_| numbers.next().unwrap())
+                })
+                .collect()
+        })
+        .collect();
+    (seeds, maps)
+}
+
+fn location(maps: &[Vec<[i64; 3]>], seed: i64) -> i64 {
+    maps.iter().fold(seed, |value, entries| {
+        entries
+            .iter()
+            .rfind(|[_, src, len]| (*src..src + len).contains(&value))
+            .map_or(value, |[dest, src, _]| value - src + dest)
+    })
+}
+
+fn part1(input: &str) -> i64 {
+    let (seeds, maps) = parse(input);
+    seeds
+        .iter()
+        .map(|&seed| location(&maps, seed))
+        .min()
+        .unwrap()
+}
+
+fn part2(input: &str) -> i64 {
+    let (seeds, maps) = parse(input);
+    seeds
+        .chunks(2)
+        .flat_map(|pair| pair[0]..pair[0] + pair[1])
+        .map(|seed| location(&maps, seed))
+        .min()
+        .unwrap()
+}
+
+#[test]
+fn last_overlapping_entry_wins() {
+    let input = "seeds: 5 1\n\nseed-to-location map:\n100 0 10\n200 0 10\n";
+    assert_eq!(part1(input), 201);
+    assert_eq!(super::part1(input), Ok(201));
+    assert_eq!(part2(input), 205);
+    assert_eq!(super::part2(input), Ok(205));
+}
+
+proptest! {
+    #[test]
+    fn solvers_match_oracle(seed: u64, size in 1..20usize, difficulty in 1..8usize) {
+        let input = generate::generate_within(seed, size, difficulty, 256);
+        let fast = super::part1(&input).map_err(TestCaseError::fail)?;
+        prop_assert_eq!(fast, part1(&input), "part 1");
+        let fast = super::part2(&input).map_err(TestCaseError::fail)?;
+        prop_assert_eq!(fast, part2(&input), "part 2");
+    }
+}
@@ -1,49 +1,386 @@
-fn main() {
+mod generate;
+#[cfg(test)]
+mod oracle;
+
+fn main() -> Result<(), String> {
+    let args = std::env::args().collect::<Vec<_>>();
+    let generated = common::generated(&args, 4, 2, generate::generate)?;
+    let input = generated.as_deref().unwrap_or(INPUT);
+    if args.iter().any(|arg| arg == "--print") {
+        print!("{}", input);
+        return Ok(());
+    }
+    let model = match args.iter().position(|arg| arg == "--model") {
+        Some(i) => model(&args[i + 1..])?,
+        None => Box::new(Standard),
+    };
     let now = std::time::Instant::now();
-    let result = part1(INPUT);
+    let result = part1(input, model.as_ref())?;
     println!("Part 1: {} [{:?}]", result, now.elapsed());
     let now = std::time::Instant::now();
-    let result = part2(INPUT);
+    let result = part2(input, model.as_ref())?;
     println!("Part 2: {} [{:?}]", result, now.elapsed());
+    Ok(())
 }
 
 const INPUT: &str = include_str!("../input.txt");
 
-fn time_n_distance(input: &str) -> Vec<(usize, usize)> {
-    let mut tmp = input.lines().map(|line| {
-        line.split(':')
-            .nth(1)
-            .unwrap()
-            .split_whitespace()
-            .map(|s| s.parse::<usize>().unwrap())
-    });
-    tmp.next().unwrap().zip(tmp.next().unwrap()).collect()
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Race {
+    time: u128,
+    record: u128,
 }
 
-fn part1(input: &str) -> usize {
-    time_n_distance(input)
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Layout {
+    // Every column is its own race
+    Columns,
+    // The digits of each row are read as one number, ignoring the spacing
+    Kerned,
+}
+
+fn row<'a>(line: Option<(usize, &'a str)>, label: &str) -> Result<(usize, Vec<&'a str>), String> {
+    let (line_no, line) = line.ok_or_else(|| format!("missing {} row", label))?;
+    let values = line
+        .strip_prefix(label)
+        .and_then(|rest| rest.strip_prefix(':'))
+        .ok_or_else(|| format!("line {}: expected {:?} label", line_no, label))?
+        .split_whitespace()
+        .collect::<Vec<_>>();
+    if values.is_empty() {
+        return Err(format!("line {}: no numbers in {} row", line_no, label));
+    }
+    if let Some(value) = values
         .iter()
-        .map(|&(time, distance)| {
-            let mut check = time / 2;
-            while check * (time - check) > distance {
-                check -= 1;
-            }
-            (check..time - check).len() as usize - 1
+        .find(|v| !v.bytes().all(|b| b.is_ascii_digit()))
+    {
+        return Err(format!("line {}: invalid number {:?}", line_no, value));
+    }
+    Ok((line_no, values))
+}
+
+fn number(digits: &str, line: usize) -> Result<u128, String> {
+    // the solvers need time^2 to fit in a u128
+    digits
+        .parse::<u64>()
+        .map(u128::from)
+        .map_err(|_| format!("line {}: number {} is too large", line, digits))
+}
+
+pub(crate) fn races(input: &str, layout: Layout) -> Result<Vec<Race>, String> {
+    let mut lines = input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line))
+        .filter(|(_, line)| !line.trim().is_empty());
+    let (time_line, times) = row(lines.next(), "Time")?;
+    let (record_line, records) = row(lines.next(), "Distance")?;
+    if let Some((line_no, _)) = lines.next() {
+        return Err(format!("line {}: unexpected trailing data", line_no));
+    }
+    if times.len() != records.len() {
+        return Err(format!(
+            "line {}: {} distances for {} times",
+            record_line,
+            records.len(),
+            times.len()
+        ));
+    }
+    match layout {
+        Layout::Columns => times
+            .iter()
+            .zip(records)
+            .map(|(time, record)| {
+                Ok(Race {
+                    time: number(time, time_line)?,
+                    record: number(record, record_line)?,
+                })
+            })
+            .collect(),
+        Layout::Kerned => Ok(vec![Race {
+            time: number(&times.concat(), time_line)?,
+            record: number(&records.concat(), record_line)?,
+        }]),
+    }
+}
+
+fn isqrt(n: u128) -> u128 {
+    if n < 2 {
+        return n;
+    }
+    let mut x = 1 << ((128 - n.leading_zeros()).div_ceil(2));
+    loop {
+        let y = (x + n / x) / 2;
+        if y >= x {
+            return x;
+        }
+        x = y;
+    }
+}
+
+// Number of hold times `h` with `h * (time - h) > record`, `time` must be below 2^64
+fn ways_to_win(time: u128, record: u128) -> u128 {
+    let distance = |hold: u128| hold * (time - hold);
+    let best = time / 2;
+    if distance(best) <= record {
+        return 0;
+    }
+    // the roots of h^2 - time*h + record are (time -+ sqrt(time^2 - 4*record)) / 2,
+    // the integer square root can only leave `lowest` a step or two off
+    let mut lowest = (time - isqrt(time * time - 4 * record)) / 2;
+    while distance(lowest) <= record {
+        lowest += 1;
+    }
+    while lowest > 0 && distance(lowest - 1) > record {
+        lowest -= 1;
+    }
+    time - 2 * lowest + 1
+}
+
+pub(crate) trait RaceModel {
+    fn distance(&self, time: u128, hold: u128) -> u128;
+
+    // Assumes `distance` rises then falls over the hold times, with ties only at the top
+    fn ways_to_win(&self, time: u128, record: u128) -> u128 {
+        let distance = |hold| self.distance(time, hold);
+        let peak = partition_point(0, time, |hold| distance(hold) < distance(hold + 1));
+        if distance(peak) <= record {
+            return 0;
+        }
+        let lowest = partition_point(0, peak, |hold| distance(hold) <= record);
+        let highest = partition_point(peak, time, |hold| distance(hold + 1) > record);
+        highest - lowest + 1
+    }
+}
+
+// First value in `low..=high` for which `pred` is false, or `high` if there is none
+fn partition_point(mut low: u128, mut high: u128, pred: impl Fn(u128) -> bool) -> u128 {
+    while low < high {
+        let mid = low + (high - low) / 2;
+        if pred(mid) {
+            low = mid + 1;
+        } else {
+            high = mid;
+        }
+    }
+    low
+}
+
+pub(crate) struct Standard;
+
+impl RaceModel for Standard {
+    fn distance(&self, time: u128, hold: u128) -> u128 {
+        hold * (time - hold)
+    }
+
+    fn ways_to_win(&self, time: u128, record: u128) -> u128 {
+        ways_to_win(time, record)
+    }
+}
+
+struct Accelerated {
+    acceleration: u128,
+}
+
+impl RaceModel for Accelerated {
+    fn distance(&self, time: u128, hold: u128) -> u128 {
+        self.acceleration * hold * (time - hold)
+    }
+
+    fn ways_to_win(&self, time: u128, record: u128) -> u128 {
+        match self.acceleration {
+            0 => 0,
+            acceleration => ways_to_win(time, record / acceleration),
+        }
+    }
+}
+
+struct Capped {
+    acceleration: u128,
+    top_speed: u128,
+}
+
+impl RaceModel for Capped {
+    fn distance(&self, time: u128, hold: u128) -> u128 {
+        (self.acceleration * hold).min(self.top_speed) * (time - hold)
+    }
+}
+
+struct Launched {
+    acceleration: u128,
+    initial_speed: u128,
+}
+
+impl RaceModel for Launched {
+    fn distance(&self, time: u128, hold: u128) -> u128 {
+        (self.initial_speed + self.acceleration * hold) * (time - hold)
+    }
+}
+
+// The boat loses `decay` speed every millisecond after it is released
+struct Decaying {
+    decay: u128,
+}
+
+impl RaceModel for Decaying {
+    fn distance(&self, time: u128, hold: u128) -> u128 {
+        let moving = match self.decay {
+            0 => time - hold,
+            decay => (time - hold).min(hold / decay + 1),
+        };
+        moving * hold - self.decay * moving * moving.saturating_sub(1) / 2
+    }
+}
+
+fn model(args: &[String]) -> Result<Box<dyn RaceModel>, String> {
+    let params = args
+        .iter()
+        .skip(1)
+        .map(|arg| {
+            arg.parse::<u128>()
+                .map_err(|_| format!("invalid parameter {:?}", arg))
         })
-        .reduce(|a, b| a * b)
-        .unwrap()
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(match (args.first().map(String::as_str), &params[..]) {
+        (Some("standard"), []) => Box::new(Standard),
+        (Some("accelerated"), &[acceleration]) => Box::new(Accelerated { acceleration }),
+        (Some("capped"), &[acceleration, top_speed]) => Box::new(Capped {
+            acceleration,
+            top_speed,
+        }),
+        (Some("launched"), &[acceleration, initial_speed]) => Box::new(Launched {
+            acceleration,
+            initial_speed,
+        }),
+        (Some("decaying"), &[decay]) => Box::new(Decaying { decay }),
+        _ => {
+            return Err("usage: --model standard | accelerated <acceleration> \
+                | capped <acceleration> <top speed> | launched <acceleration> <initial speed> \
+                | decaying <decay>"
+                .to_string())
+        }
+    })
+}
+
+fn ways_to_win_all(races: &[Race], model: &dyn RaceModel) -> Result<u128, String> {
+    races.iter().try_fold(1u128, |product, race| {
+        product
+            .checked_mul(model.ways_to_win(race.time, race.record))
+            .ok_or_else(|| "product of the ways to win overflows".to_string())
+    })
+}
+
+pub(crate) fn part1(input: &str, model: &dyn RaceModel) -> Result<u128, String> {
+    ways_to_win_all(&races(input, Layout::Columns)?, model)
 }
 
-fn part2(input: &str) -> usize {
-    let (time, distance) =
-        time_n_distance(&input.chars().filter(|&c| c != ' ').collect::<String>())[0];
-    let mut check = time / 2;
-    while check * (time - check) > distance {
-        check /= 2;
+pub(crate) fn part2(input: &str, model: &dyn RaceModel) -> Result<u128, String> {
+    ways_to_win_all(&races(input, Layout::Kerned)?, model)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn brute_force(model: &dyn RaceModel, time: u128, record: u128) -> u128 {
+        (0..=time)
+            .filter(|&hold| model.distance(time, hold) > record)
+            .count() as u128
+    }
+
+    #[test]
+    fn rejects_malformed_rows() {
+        let error = |input: &str| races(input, Layout::Columns).unwrap_err();
+        assert_eq!(error("Time: 7\n"), "missing Distance row");
+        assert_eq!(
+            error("Times: 7\nDistance: 9\n"),
+            "line 1: expected \"Time\" label"
+        );
+        assert_eq!(
+            error("Time:\nDistance:\n"),
+            "line 1: no numbers in Time row"
+        );
+        assert_eq!(
+            error("Time: 7\nDistance:\n"),
+            "line 2: no numbers in Distance row"
+        );
+        assert_eq!(
+            error("Time: 7 1x\nDistance: 9 2\n"),
+            "line 1: invalid number \"1x\""
+        );
+        assert_eq!(
+            error("Time: 7 15\nDistance: 9\n"),
+            "line 2: 1 distances for 2 times"
+        );
+        assert_eq!(
+            error("Time: 7\nDistance: 9\n\nTime: 1\n"),
+            "line 4: unexpected trailing data"
+        );
+        assert_eq!(
+            races(
+                "Time: 9999999999 99999999999\nDistance: 1 2\n",
+                Layout::Kerned
+            )
+            .unwrap_err(),
+            "line 1: number 999999999999999999999 is too large"
+        );
     }
-    while check * (time - check) < distance {
-        check += 1;
+
+    #[test]
+    fn product_overflow_is_an_error() {
+        let max = u64::MAX;
+        let input = format!("Time: {} {} {}\nDistance: 0 0 0\n", max, max, max);
+        assert_eq!(
+            part1(&input, &Standard),
+            Err("product of the ways to win overflows".to_string())
+        );
+    }
+
+    #[test]
+    fn models_match_brute_force() {
+        let models: Vec<(&str, Box<dyn RaceModel>)> = vec![
+            ("standard", Box::new(Standard)),
+            ("accelerated 3", Box::new(Accelerated { acceleration: 3 })),
+            (
+                "capped 2 15",
+                Box::new(Capped {
+                    acceleration: 2,
+                    top_speed: 15,
+                }),
+            ),
+            (
+                "launched 1 7",
+                Box::new(Launched {
+                    acceleration: 1,
+                    initial_speed: 7,
+                }),
+            ),
+            (
+                "launched 0 4",
+                Box::new(Launched {
+                    acceleration: 0,
+                    initial_speed: 4,
+                }),
+            ),
+            ("decaying 3", Box::new(Decaying { decay: 3 })),
+        ];
+        for (name, model) in &models {
+            for time in 0..100 {
+                let max = (0..=time)
+                    .map(|hold| model.distance(time, hold))
+                    .max()
+                    .unwrap();
+                for record in 0..=max + 1 {
+                    assert_eq!(
+                        model.ways_to_win(time, record),
+                        brute_force(model.as_ref(), time, record),
+                        "{}: time {}, record {}",
+                        name,
+                        time,
+                        record
+                    );
+                }
+            }
+        }
     }
-    check -= 1;
-    (check..time - check).len() as usize - 1
 }
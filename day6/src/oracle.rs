@@ -0,0 +1,48 @@
+use proptest::prelude::*;
+use proptest::test_runner::TestCaseError;
+
+use super::{generate, Standard};
+
+// Slow but obviously right: the rows read straight off the text, and every
+// hold time tried in turn
+fn rows(input: &str) -> (Vec<&str>, Vec<&str>) {
+    let mut lines = input.lines().map(|line| {
+        let (_, values) = line.split_once(':').unwrap();
+        values.split_whitespace().collect::<Vec<_>>()
+    });
+    (lines.next().unwrap(), lines.next().unwrap())
+}
+
+fn ways(time: u64, record: u64) -> u64 {
+    (0..=time)
+        .filter(|hold| hold * (time - hold) > record)
+        .count() as u64
+}
+
+fn part1(input: &str) -> u64 {
+    let (times, records) = rows(input);
+    times
+        .iter()
+        .zip(records)
+        .map(|(time, record)| ways(time.parse().unwrap(), record.parse().unwrap()))
+        .product()
+}
+
+fn part2(input: &str) -> u64 {
+    let (times, records) = rows(input);
+    ways(
+        times.concat().parse().unwrap(),
+        records.concat().parse().unwrap(),
+    )
+}
+
+proptest! {
+    #[test]
+    fn solvers_match_oracle(seed: u64, size in 1..=3usize, difficulty in 1..=2usize) {
+        let input = generate::generate(seed, size, difficulty);
+        let fast = super::part1(&input, &Standard).map_err(TestCaseError::fail)?;
+        prop_assert_eq!(fast, part1(&input) as u128, "part 1");
+        let fast = super::part2(&input, &Standard).map_err(TestCaseError::fail)?;
+        prop_assert_eq!(fast, part2(&input) as u128, "part 2");
+    }
+}
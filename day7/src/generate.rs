@@ -0,0 +1,24 @@
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use super::Card;
+
+// `size` hands of `hand_size` cards, each card a joker `difficulty` times out
+// of 20; bids are scaled down so the winnings still fit in a u32
+pub fn generate(seed: u64, size: usize, difficulty: usize, hand_size: usize) -> String {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let most = (u32::MAX as usize / (size * (size + 1) / 2).max(1)).clamp(1, 1000);
+    let mut input = String::new();
+    for _ in 0..size {
+        for _ in 0..hand_size {
+            let card = if rng.gen_range(0..20) < difficulty {
+                Card::J
+            } else {
+                Card::from_value(rng.gen_range(0..Card::COUNT as u8))
+            };
+            input.push_str(&card.to_string());
+        }
+        input.push_str(&format!(" {}\n", rng.gen_range(1..=most)));
+    }
+    input
+}
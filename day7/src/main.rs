@@ -1,14 +1,90 @@
-use std::{cmp::Ordering, str::FromStr};
+use std::cmp::Ordering;
 
-use rayon::prelude::*;
+mod generate;
+#[cfg(test)]
+mod oracle;
+mod poker;
 
-fn main() {
-    let now = std::time::Instant::now();
-    let result = part1(INPUT);
-    println!("Part 1: {} [{:?}]", result, now.elapsed());
-    let now = std::time::Instant::now();
-    let result = part2(INPUT);
-    println!("Part 2: {} [{:?}]", result, now.elapsed());
+fn main() -> Result<(), String> {
+    let args = std::env::args().collect::<Vec<_>>();
+    if let Some(i) = args.iter().position(|arg| arg == "--poker") {
+        return rank_poker_hands(&args[i + 1..]);
+    }
+    let rules = rules(&args)?;
+    let generated = common::generated(&args, 1000, 2, |seed, size, difficulty| {
+        generate::generate(seed, size, difficulty, rules.hand_size)
+    })?;
+    let input = generated.as_deref().unwrap_or(INPUT);
+    if args.iter().any(|arg| arg == "--print") {
+        print!("{}", input);
+        return Ok(());
+    }
+    if let Some(i) = args.iter().position(|arg| arg == "--explain") {
+        let (Some(a), Some(b)) = (args.get(i + 1), args.get(i + 2)) else {
+            return Err("usage: --explain <hand> <hand>".to_string());
+        };
+        let a = Hand::new(parse_cards(a, rules.hand_size)?, &rules);
+        let b = Hand::new(parse_cards(b, rules.hand_size)?, &rules);
+        println!("{}", explain(&a, &b, &rules));
+        return Ok(());
+    }
+    if args.iter().any(|arg| arg == "--ranking") {
+        return print_ranking(input, &rules);
+    }
+    // the puzzle parts always deal 5 cards
+    if rules.hand_size == 5 {
+        let now = std::time::Instant::now();
+        let result = part1(input)?;
+        println!("Part 1: {} [{:?}]", result, now.elapsed());
+        let now = std::time::Instant::now();
+        let result = part2(input)?;
+        println!("Part 2: {} [{:?}]", result, now.elapsed());
+    }
+    if args
+        .iter()
+        .any(|arg| arg == "--wild" || arg == "--hand-size")
+    {
+        let now = std::time::Instant::now();
+        let result = helper(parse(input, &rules)?);
+        println!("Custom: {} [{:?}]", result, now.elapsed());
+    }
+    Ok(())
+}
+
+// `--wild <cards> [--wild-lowest]` for a custom rule set, `--jokers` for
+// part 2's, either with `--hand-size <n>` for hands other than 5 cards
+fn rules(args: &[String]) -> Result<Rules, String> {
+    let hand_size = match args.iter().position(|arg| arg == "--hand-size") {
+        Some(i) => {
+            let size = args.get(i + 1).map_or("", String::as_str);
+            size.parse()
+                .map_err(|_| format!("invalid hand size {:?}", size))?
+        }
+        None => 5,
+    };
+    let rules = Rules::with_hand_size(hand_size);
+    Ok(
+        if let Some(i) = args.iter().position(|arg| arg == "--wild") {
+            Rules {
+                wild: args
+                    .get(i + 1)
+                    .map_or("", String::as_str)
+                    .chars()
+                    .map(Card::try_from)
+                    .collect::<Result<_, _>>()?,
+                wild_lowest: args.iter().any(|arg| arg == "--wild-lowest"),
+                ..rules
+            }
+        } else if args.iter().any(|arg| arg == "--jokers") {
+            Rules {
+                wild: vec![Card::J],
+                wild_lowest: true,
+                ..rules
+            }
+        } else {
+            rules
+        },
+    )
 }
 
 const INPUT: &str = include_str!("../input.txt");
@@ -25,243 +101,398 @@ enum Card {
 
 impl PartialOrd for Card {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.value(false).cmp(&other.value(false)))
+        Some(self.cmp(other))
     }
 }
 
 impl Ord for Card {
     fn cmp(&self, other: &Self) -> Ordering {
-        self.partial_cmp(other).unwrap()
+        self.value().cmp(&other.value())
     }
 }
 
 impl Card {
     const COUNT: usize = 13;
 
-    fn value(&self, joker: bool) -> u8 {
-        if joker {
-            if self == &Self::J {
-                0
-            } else {
-                self.value(false) + 1
-            }
-        } else {
-            match self {
-                Self::N(n) => n - 2,
-                Self::T => 8,
-                Self::J => 9,
-                Self::Q => 10,
-                Self::K => 11,
-                Self::A => 12,
-            }
+    fn value(&self) -> u8 {
+        match self {
+            Self::N(n) => n - 2,
+            Self::T => 8,
+            Self::J => 9,
+            Self::Q => 10,
+            Self::K => 11,
+            Self::A => 12,
         }
     }
 
     fn from_value(value: u8) -> Self {
         match value {
-            0 => Self::J,
-            1 => Self::N(2),
-            2 => Self::N(3),
-            3 => Self::N(4),
-            4 => Self::N(5),
-            5 => Self::N(6),
-            6 => Self::N(7),
-            7 => Self::N(8),
+            0..=7 => Self::N(value + 2),
             8 => Self::T,
-            9 => Self::Q,
-            10 => Self::K,
-            11 => Self::A,
+            9 => Self::J,
+            10 => Self::Q,
+            11 => Self::K,
+            12 => Self::A,
             _ => panic!("Invalid value"),
         }
     }
 }
 
-impl From<char> for Card {
-    fn from(c: char) -> Self {
+impl std::fmt::Display for Card {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::A => write!(f, "A"),
+            Self::K => write!(f, "K"),
+            Self::Q => write!(f, "Q"),
+            Self::J => write!(f, "J"),
+            Self::T => write!(f, "T"),
+            Self::N(n) => write!(f, "{}", n),
+        }
+    }
+}
+
+impl TryFrom<char> for Card {
+    type Error = String;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
         match c {
-            'A' => Self::A,
-            'K' => Self::K,
-            'Q' => Self::Q,
-            'J' => Self::J,
-            'T' => Self::T,
-            n => Self::N(n.to_digit(10).unwrap() as u8),
+            'A' => Ok(Self::A),
+            'K' => Ok(Self::K),
+            'Q' => Ok(Self::Q),
+            'J' => Ok(Self::J),
+            'T' => Ok(Self::T),
+            '2'..='9' => Ok(Self::N(c as u8 - b'0')),
+            _ => Err(format!("invalid card {:?}", c)),
         }
     }
 }
 
-fn parse_cards(input: &str) -> [Card; 5] {
-    input
+fn parse_cards(input: &str, hand_size: usize) -> Result<Vec<Card>, String> {
+    let cards = input
         .chars()
-        .enumerate()
-        .fold([Card::A; 5], |mut acc, (i, c)| {
-            acc[i] = Card::from(c);
-            acc
-        })
+        .map(Card::try_from)
+        .collect::<Result<Vec<_>, _>>()?;
+    if cards.len() != hand_size {
+        return Err(format!(
+            "expected {} cards, got {} in {:?}",
+            hand_size,
+            cards.len(),
+            input
+        ));
+    }
+    Ok(cards)
 }
 
-#[derive(Debug, Ord, PartialOrd, Eq, PartialEq)]
-enum HandType {
-    HighCard,
-    OnePair,
-    TwoPair,
-    ThreeOfAKind,
-    FullHouse,
-    FourOfAKind,
-    FiveOfAKind,
+#[derive(Debug, Clone)]
+struct Category {
+    name: String,
+    // Group sizes a hand needs, largest first, e.g. [3, 2] for a full house
+    groups: Vec<u8>,
 }
 
-impl HandType {
-    fn from_with_joker(cards: [Card; 5]) -> Self {
-        let joker_count = cards.iter().filter(|c| c == &&Card::J).count();
-        let no_joker = cards
+impl Category {
+    fn new(groups: Vec<u8>) -> Self {
+        const WORDS: [&str; 9] = [
+            "zero", "one", "two", "three", "four", "five", "six", "seven", "eight",
+        ];
+        let name = match groups[..] {
+            [] => "high card".to_string(),
+            [2] => "one pair".to_string(),
+            [2, 2] => "two pair".to_string(),
+            [3, 2] => "full house".to_string(),
+            [n] => match WORDS.get(n as usize) {
+                Some(word) => format!("{} of a kind", word),
+                None => format!("{} of a kind", n),
+            },
+            _ => format!(
+                "groups of {}",
+                groups
+                    .iter()
+                    .map(|n| n.to_string())
+                    .collect::<Vec<_>>()
+                    .join("+")
+            ),
+        };
+        Self { name, groups }
+    }
+
+    // How many wild cards it takes to turn `groups` (largest first) into this category
+    fn shortfall(&self, groups: &[u8]) -> u8 {
+        self.groups
             .iter()
-            .filter(|c| c != &&Card::J)
-            .copied()
-            .fold(([Card::A; 5], 0), |mut acc, card| {
-                if card != Card::J {
-                    acc.0[acc.1] = card;
-                    acc.1 += 1;
-                }
-                acc
-            })
-            .0;
-        if joker_count == 0 {
-            Self::from(no_joker)
-        } else {
-            (1..(Card::COUNT - 1).pow(joker_count as u32))
-                .into_par_iter()
-                .map(|i| {
-                    let mut no_joker = no_joker;
-                    for j in 0..joker_count {
-                        no_joker[4 - j] = Card::from_value(
-                            ((i / (Card::COUNT - 1).pow(j as u32)) % (Card::COUNT - 1)) as u8,
-                        );
-                    }
-                    Self::from(no_joker)
-                })
-                .map(|cards| Self::from(cards))
-                .max()
-                .unwrap()
-        }
+            .enumerate()
+            .map(|(i, &needed)| needed.saturating_sub(groups.get(i).copied().unwrap_or(0)))
+            .sum()
     }
 }
 
-impl From<[Card; 5]> for HandType {
-    fn from(hand: [Card; 5]) -> Self {
-        let mut counts = [0; 13];
-        for card in hand.iter() {
-            counts[card.value(false) as usize] += 1;
-        }
+#[derive(Debug, Clone)]
+pub(crate) struct Rules {
+    // Lowest rank first
+    ranks: Vec<Card>,
+    wild: Vec<Card>,
+    // Whether wild cards rank below every other card when breaking ties
+    wild_lowest: bool,
+    hand_size: usize,
+    // Lowest category first, the first one has to match any hand
+    ladder: Vec<Category>,
+}
 
-        let mut pairs = 0;
-        let mut three_of_a_kind = false;
-        let mut four_of_a_kind = false;
-        let mut five_of_a_kind = false;
-
-        for count in counts.iter() {
-            match count {
-                2 => pairs += 1,
-                3 => three_of_a_kind = true,
-                4 => four_of_a_kind = true,
-                5 => five_of_a_kind = true,
-                _ => (),
+impl Rules {
+    pub(crate) fn standard() -> Self {
+        Self::with_hand_size(5)
+    }
+
+    // The categories are every way of splitting the hand into groups of equal
+    // cards, ordered by the largest group, then the next largest and so on
+    fn with_hand_size(hand_size: usize) -> Self {
+        fn partitions(left: usize, max: usize, groups: &mut Vec<u8>, ladder: &mut Vec<Vec<u8>>) {
+            if left == 0 {
+                ladder.push(groups.iter().copied().filter(|&n| n > 1).collect());
             }
+            for n in (1..=left.min(max)).rev() {
+                groups.push(n as u8);
+                partitions(left - n, n, groups, ladder);
+                groups.pop();
+            }
+        }
+
+        let mut ladder = Vec::new();
+        partitions(hand_size, hand_size, &mut Vec::new(), &mut ladder);
+        ladder.sort();
+        Self {
+            ranks: (0..Card::COUNT as u8).map(Card::from_value).collect(),
+            wild: Vec::new(),
+            wild_lowest: false,
+            hand_size,
+            ladder: ladder.into_iter().map(Category::new).collect(),
+        }
+    }
+
+    pub(crate) fn jokers() -> Self {
+        Self {
+            wild: vec![Card::J],
+            wild_lowest: true,
+            ..Self::standard()
+        }
+    }
+
+    #[cfg(test)]
+    fn deuces_wild() -> Self {
+        Self {
+            wild: vec![Card::N(2)],
+            ..Self::standard()
         }
+    }
 
-        if five_of_a_kind {
-            Self::FiveOfAKind
-        } else if four_of_a_kind {
-            Self::FourOfAKind
-        } else if three_of_a_kind && pairs == 1 {
-            Self::FullHouse
-        } else if three_of_a_kind {
-            Self::ThreeOfAKind
-        } else if pairs == 2 {
-            Self::TwoPair
-        } else if pairs == 1 {
-            Self::OnePair
+    fn is_wild(&self, card: Card) -> bool {
+        self.wild.contains(&card)
+    }
+
+    fn rank(&self, card: Card) -> usize {
+        let ranks = self.ranks.iter();
+        if self.wild_lowest {
+            let (wild, natural): (Vec<_>, Vec<_>) = ranks.partition(|&&c| self.is_wild(c));
+            wild.into_iter().chain(natural).position(|&c| c == card)
         } else {
-            Self::HighCard
+            self.ranks.iter().position(|&c| c == card)
         }
+        .unwrap()
+    }
+
+    // Group sizes of the natural cards, largest first, and the number of wild cards
+    fn groups(&self, cards: &[Card], use_wild: bool) -> (Vec<u8>, u8) {
+        let mut counts = vec![0; self.ranks.len()];
+        let mut wild = 0;
+        for &card in cards {
+            if use_wild && self.is_wild(card) {
+                wild += 1;
+            } else {
+                counts[self.ranks.iter().position(|&c| c == card).unwrap()] += 1;
+            }
+        }
+        counts.retain(|&count| count > 0);
+        counts.sort_unstable_by(|a, b| b.cmp(a));
+        (counts, wild)
+    }
+
+    fn category(&self, cards: &[Card]) -> usize {
+        let (groups, wild) = self.groups(cards, true);
+        self.ladder
+            .iter()
+            .rposition(|category| category.shortfall(&groups) <= wild)
+            .unwrap()
+    }
+
+    // What each wild card stands for in the best hand, wild cards that aren't
+    // needed for the category join the largest group
+    fn resolve(&self, cards: &[Card]) -> Vec<Card> {
+        let mut groups = self
+            .ranks
+            .iter()
+            .rev()
+            .map(|&rank| {
+                let count = cards
+                    .iter()
+                    .filter(|&&card| card == rank && !self.is_wild(card))
+                    .count();
+                (count as u8, rank)
+            })
+            .filter(|&(count, _)| count > 0)
+            .collect::<Vec<_>>();
+        groups.sort_by_key(|&(count, _)| std::cmp::Reverse(count));
+        let mut unused =
+            self.ranks.iter().rev().filter(|&&rank| {
+                !self.is_wild(rank) && groups.iter().all(|&(_, card)| card != rank)
+            });
+        let mut targets = Vec::new();
+        for (i, &needed) in self.ladder[self.category(cards)].groups.iter().enumerate() {
+            match groups.get(i) {
+                Some(&(count, rank)) => targets.extend(std::iter::repeat_n(
+                    rank,
+                    needed.saturating_sub(count) as usize,
+                )),
+                None => {
+                    let rank = *unused.next().unwrap_or(&self.ranks[self.ranks.len() - 1]);
+                    targets.extend(std::iter::repeat_n(rank, needed as usize));
+                }
+            }
+        }
+        let largest = groups
+            .first()
+            .map(|&(_, rank)| rank)
+            .or(targets.first().copied())
+            .unwrap_or(self.ranks[self.ranks.len() - 1]);
+        let mut targets = targets.into_iter();
+        cards
+            .iter()
+            .map(|&card| {
+                if self.is_wild(card) {
+                    targets.next().unwrap_or(largest)
+                } else {
+                    card
+                }
+            })
+            .collect()
     }
 }
 
 #[derive(Debug, Eq, PartialEq)]
-struct Hand {
-    cards: [Card; 5],
-    hand_type: HandType,
-    joker: bool,
+pub(crate) struct Hand {
+    cards: Vec<Card>,
+    // Index into the rules' ladder
+    category: usize,
+    ranks: Vec<usize>,
 }
 
 impl Hand {
-    fn from_with_joker(cards: [Card; 5]) -> Self {
+    fn new(cards: Vec<Card>, rules: &Rules) -> Self {
         Self {
-            joker: true,
+            category: rules.category(&cards),
+            ranks: cards.iter().map(|&card| rules.rank(card)).collect(),
             cards,
-            hand_type: HandType::from_with_joker(cards),
         }
     }
 }
 
-impl FromStr for Hand {
-    type Err = ();
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let cards = parse_cards(s);
-
-        Ok(Self {
-            joker: false,
-            cards,
-            hand_type: HandType::from(cards),
-        })
-    }
-}
-
 impl PartialOrd for Hand {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        let type_cmp = self.hand_type.cmp(&other.hand_type);
-        if type_cmp.is_eq() {
-            for i in 0..5 {
-                let card_cmp = self.cards[i].cmp(&other.cards[i]);
-                if card_cmp.is_gt() {
-                    return Some(Ordering::Greater);
-                } else if card_cmp.is_lt() {
-                    return Some(Ordering::Less);
-                }
-            }
-            Some(Ordering::Equal)
-        } else {
-            Some(type_cmp)
-        }
+        Some(self.cmp(other))
     }
 }
 
 impl Ord for Hand {
     fn cmp(&self, other: &Self) -> Ordering {
-        self.partial_cmp(other).unwrap()
+        self.category
+            .cmp(&other.category)
+            .then_with(|| self.ranks.cmp(&other.ranks))
     }
 }
 
-fn parse(input: &str) -> Vec<(Hand, u32)> {
-    input
-        .lines()
-        .map(|line| {
-            let mut split = line.split(' ');
-            let hand = split.next().unwrap().parse().unwrap();
-            let value = split.next().unwrap().parse().unwrap();
-            (hand, value)
-        })
-        .collect()
+fn cards_to_string(cards: &[Card]) -> String {
+    cards.iter().map(|card| card.to_string()).collect()
+}
+
+fn describe(hand: &Hand, rules: &Rules) -> String {
+    let category = &rules.ladder[hand.category].name;
+    if hand.cards.iter().any(|&card| rules.is_wild(card)) {
+        let resolved = cards_to_string(&rules.resolve(&hand.cards));
+        format!(
+            "{} ({} as {})",
+            cards_to_string(&hand.cards),
+            category,
+            resolved
+        )
+    } else {
+        format!("{} ({})", cards_to_string(&hand.cards), category)
+    }
+}
+
+fn explain(a: &Hand, b: &Hand, rules: &Rules) -> String {
+    let (winner, loser) = match a.cmp(b) {
+        Ordering::Less => (b, a),
+        Ordering::Greater => (a, b),
+        Ordering::Equal => {
+            return format!("{} ties with {}", describe(a, rules), describe(b, rules))
+        }
+    };
+    let reason = if winner.category != loser.category {
+        format!(
+            "{} beats {}",
+            rules.ladder[winner.category].name, rules.ladder[loser.category].name
+        )
+    } else {
+        let i = (0..winner.ranks.len())
+            .find(|&i| winner.ranks[i] != loser.ranks[i])
+            .unwrap();
+        format!(
+            "same category, card {} {} beats {}",
+            i + 1,
+            winner.cards[i],
+            loser.cards[i]
+        )
+    };
+    format!(
+        "{} beats {}: {}",
+        describe(winner, rules),
+        describe(loser, rules),
+        reason
+    )
 }
 
-fn parse_joker(input: &str) -> Vec<(Hand, u32)> {
+fn print_ranking(input: &str, rules: &Rules) -> Result<(), String> {
+    let mut hands = parse(input, rules)?;
+    hands.sort_by(|(a, _), (b, _)| a.cmp(b));
+    let mut total = 0;
+    for (i, (hand, bid)) in hands.iter().enumerate() {
+        let winnings = bid * (i as u32 + 1);
+        total += winnings;
+        println!(
+            "{:>5} {:>5} {:>8}  {}",
+            i + 1,
+            bid,
+            winnings,
+            describe(hand, rules)
+        );
+    }
+    println!("Total winnings: {}", total);
+    Ok(())
+}
+
+pub(crate) fn parse(input: &str, rules: &Rules) -> Result<Vec<(Hand, u32)>, String> {
     input
         .lines()
-        .map(|line| {
-            let mut split = line.split(' ');
-            let hand = Hand::from_with_joker(parse_cards(split.next().unwrap()));
-            let value = split.next().unwrap().parse().unwrap();
-            (hand, value)
+        .enumerate()
+        .map(|(i, line)| {
+            let (cards, bid) = line
+                .split_once(' ')
+                .ok_or_else(|| format!("line {}: expected a hand and a bid", i + 1))?;
+            let cards = parse_cards(cards, rules.hand_size)
+                .map_err(|e| format!("line {}: {}", i + 1, e))?;
+            let bid = bid
+                .parse()
+                .map_err(|_| format!("line {}: invalid bid {:?}", i + 1, bid))?;
+            Ok((Hand::new(cards, rules), bid))
         })
         .collect()
 }
@@ -275,10 +506,144 @@ fn helper(mut hands: Vec<(Hand, u32)>) -> u32 {
         .fold(0, |acc, (i, value)| acc + (value * (i as u32 + 1)))
 }
 
-fn part1(input: &str) -> u32 {
-    helper(parse(input))
+// Each argument is a comma separated list of at least 5 cards like `AS,KD,TH,2C,2S`
+fn rank_poker_hands(hands: &[String]) -> Result<(), String> {
+    let mut ranked = hands
+        .iter()
+        .map(|hand| {
+            let cards = hand
+                .split(',')
+                .map(str::parse)
+                .collect::<Result<Vec<poker::PokerCard>, _>>()?;
+            let best =
+                poker::best(&cards).ok_or_else(|| format!("{:?} has fewer than 5 cards", hand))?;
+            Ok((best, hand))
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+    ranked.sort_by(|(a, _), (b, _)| b.cmp(a));
+    for (best, hand) in ranked {
+        println!("{}: {}", hand, poker::CATEGORIES[best.category]);
+    }
+    Ok(())
 }
 
-fn part2(input: &str) -> u32 {
-    helper(parse_joker(input))
+pub(crate) fn part1(input: &str) -> Result<u32, String> {
+    Ok(helper(parse(input, &Rules::standard())?))
+}
+
+pub(crate) fn part2(input: &str) -> Result<u32, String> {
+    Ok(helper(parse(input, &Rules::jokers())?))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{HashMap, HashSet};
+
+    use rayon::prelude::*;
+
+    use super::*;
+
+    // Tries every multiset of ranks in place of the wild cards, which of them
+    // goes where doesn't change the category
+    fn category_brute_force(rules: &Rules, cards: &[Card]) -> usize {
+        let wild = cards
+            .iter()
+            .enumerate()
+            .filter(|(_, &card)| rules.is_wild(card))
+            .map(|(i, _)| i)
+            .collect::<Vec<_>>();
+        let mut choice = vec![0; wild.len()];
+        let mut best = 0;
+        loop {
+            let mut cards = cards.to_vec();
+            for (&position, &rank) in wild.iter().zip(&choice) {
+                cards[position] = rules.ranks[rank];
+            }
+            let (groups, _) = rules.groups(&cards, false);
+            let category = rules
+                .ladder
+                .iter()
+                .rposition(|category| category.shortfall(&groups) == 0)
+                .unwrap();
+            best = best.max(category);
+            // next non-decreasing choice, like an odometer
+            let Some(j) = choice
+                .iter()
+                .rposition(|&rank| rank + 1 < rules.ranks.len())
+            else {
+                return best;
+            };
+            let rank = choice[j] + 1;
+            choice[j..].fill(rank);
+        }
+    }
+
+    // Compares the wild card classification against brute force on every possible
+    // hand, the category doesn't depend on card order so each multiset of ranks
+    // is brute forced only once
+    fn check(name: &str, rules: &Rules) {
+        let hands = (0..Card::COUNT.pow(rules.hand_size as u32))
+            .map(|i| {
+                let mut multiset = (0..rules.hand_size)
+                    .map(|j| i / Card::COUNT.pow(j as u32) % Card::COUNT)
+                    .collect::<Vec<_>>();
+                let cards = multiset
+                    .iter()
+                    .map(|&index| rules.ranks[index])
+                    .collect::<Vec<_>>();
+                multiset.sort_unstable();
+                (cards, multiset)
+            })
+            .collect::<Vec<_>>();
+        let multisets = hands
+            .iter()
+            .map(|(_, multiset)| multiset)
+            .collect::<HashSet<_>>();
+        let brute_force = multisets
+            .into_par_iter()
+            .map(|multiset| {
+                let cards = multiset
+                    .iter()
+                    .map(|&index| rules.ranks[index])
+                    .collect::<Vec<_>>();
+                (multiset, category_brute_force(rules, &cards))
+            })
+            .collect::<HashMap<_, _>>();
+        for (cards, multiset) in &hands {
+            assert_eq!(
+                rules.category(cards),
+                brute_force[multiset],
+                "{}: {:?}",
+                name,
+                cards
+            );
+        }
+    }
+
+    #[test]
+    fn small_hands_match_brute_force() {
+        let jokers = Rules {
+            wild: vec![Card::J],
+            wild_lowest: true,
+            ..Rules::with_hand_size(4)
+        };
+        check("jokers, 4 cards", &jokers);
+        let jokers_and_deuces = Rules {
+            wild: vec![Card::J, Card::N(2)],
+            ..Rules::with_hand_size(3)
+        };
+        check("jokers and deuces wild, 3 cards", &jokers_and_deuces);
+    }
+
+    #[test]
+    fn five_card_hands_match_brute_force() {
+        check("standard", &Rules::standard());
+        check("jokers", &Rules::jokers());
+        check("deuces wild", &Rules::deuces_wild());
+        let jokers_and_deuces = Rules {
+            wild: vec![Card::J, Card::N(2)],
+            ..Rules::jokers()
+        };
+        check("jokers and deuces wild", &jokers_and_deuces);
+    }
 }
@@ -0,0 +1,73 @@
+use proptest::prelude::*;
+use proptest::test_runner::TestCaseError;
+
+use super::generate;
+
+// Slow but obviously right: hands typed from their sorted card counts, and
+// every joker tried as every other card
+fn kind(cards: &[u8]) -> u8 {
+    let mut counts = cards
+        .iter()
+        .map(|card| cards.iter().filter(|&other| other == card).count())
+        .collect::<Vec<_>>();
+    counts.sort_unstable_by(|a, b| b.cmp(a));
+    match counts[..] {
+        [5, ..] => 6,
+        [4, ..] => 5,
+        [3, 3, 3, 2, 2] => 4,
+        [3, ..] => 3,
+        [2, 2, 2, 2, 1] => 2,
+        [2, ..] => 1,
+        _ => 0,
+    }
+}
+
+fn best_kind(cards: &[u8], joker: u8) -> u8 {
+    match cards.iter().position(|&card| card == joker) {
+        Some(i) => (0..13)
+            .filter(|&card| card != joker)
+            .map(|card| {
+                let mut cards = cards.to_vec();
+                cards[i] = card;
+                best_kind(&cards, joker)
+            })
+            .max()
+            .unwrap(),
+        None => kind(cards),
+    }
+}
+
+fn winnings(input: &str, order: &str, jokers: bool) -> u32 {
+    let mut hands = input
+        .lines()
+        .map(|line| {
+            let (cards, bid) = line.split_once(' ').unwrap();
+            let cards = cards
+                .chars()
+                .map(|c| order.find(c).unwrap() as u8)
+                .collect::<Vec<_>>();
+            let kind = match jokers {
+                true => best_kind(&cards, 0),
+                false => kind(&cards),
+            };
+            ((kind, cards), bid.parse::<u32>().unwrap())
+        })
+        .collect::<Vec<_>>();
+    hands.sort_by(|a, b| a.0.cmp(&b.0));
+    hands
+        .iter()
+        .enumerate()
+        .map(|(i, (_, bid))| bid * (i as u32 + 1))
+        .sum()
+}
+
+proptest! {
+    #[test]
+    fn solvers_match_oracle(seed: u64, size in 1..40usize, difficulty in 0..10usize) {
+        let input = generate::generate(seed, size, difficulty, 5);
+        let fast = super::part1(&input).map_err(TestCaseError::fail)?;
+        prop_assert_eq!(fast, winnings(&input, "23456789TJQKA", false), "part 1");
+        let fast = super::part2(&input).map_err(TestCaseError::fail)?;
+        prop_assert_eq!(fast, winnings(&input, "J23456789TQKA", true), "part 2");
+    }
+}
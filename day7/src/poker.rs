@@ -0,0 +1,171 @@
+use std::str::FromStr;
+
+use super::{Card, Hand};
+
+pub const CATEGORIES: [&str; 9] = [
+    "high card",
+    "one pair",
+    "two pair",
+    "three of a kind",
+    "straight",
+    "flush",
+    "full house",
+    "four of a kind",
+    "straight flush",
+];
+
+const THREE_OF_A_KIND: usize = 3;
+const STRAIGHT: usize = 4;
+const FLUSH: usize = 5;
+const FULL_HOUSE: usize = 6;
+const FOUR_OF_A_KIND: usize = 7;
+const STRAIGHT_FLUSH: usize = 8;
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum Suit {
+    Clubs,
+    Diamonds,
+    Hearts,
+    Spades,
+}
+
+#[cfg(test)]
+impl Suit {
+    pub const ALL: [Suit; 4] = [Self::Clubs, Self::Diamonds, Self::Hearts, Self::Spades];
+}
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub struct PokerCard {
+    pub rank: Card,
+    pub suit: Suit,
+}
+
+impl FromStr for PokerCard {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        let (Some(rank), Some(suit), None) = (chars.next(), chars.next(), chars.next()) else {
+            return Err(format!("expected a rank and a suit, got {:?}", s));
+        };
+        let rank = Card::try_from(rank).map_err(|_| format!("invalid rank in {:?}", s))?;
+        let suit = match suit {
+            'C' => Suit::Clubs,
+            'D' => Suit::Diamonds,
+            'H' => Suit::Hearts,
+            'S' => Suit::Spades,
+            _ => return Err(format!("invalid suit in {:?}", s)),
+        };
+        Ok(Self { rank, suit })
+    }
+}
+
+// Ranks the hand with the usual poker categories, ties are broken by the
+// ranks of the biggest groups first and then by kickers
+pub fn evaluate(cards: [PokerCard; 5]) -> Hand {
+    let mut counts = [0; Card::COUNT];
+    for card in cards {
+        counts[card.rank.value() as usize] += 1;
+    }
+    let mut ranks = (0..Card::COUNT)
+        .rev()
+        .flat_map(|value| std::iter::repeat_n(value, counts[value]))
+        .collect::<Vec<_>>();
+    ranks.sort_by_key(|&value| std::cmp::Reverse(counts[value]));
+
+    let flush = cards.iter().all(|card| card.suit == cards[0].suit);
+    let distinct = counts.iter().filter(|&&count| count > 0).count() == 5;
+    let straight_high = if distinct && ranks[0] - ranks[4] == 4 {
+        Some(ranks[0])
+    } else if distinct && ranks == [12, 3, 2, 1, 0] {
+        // ace-low, the five is the high card
+        Some(3)
+    } else {
+        None
+    };
+
+    let category = match (straight_high, flush, counts.iter().max()) {
+        (Some(_), true, _) => STRAIGHT_FLUSH,
+        (_, _, Some(4)) => FOUR_OF_A_KIND,
+        _ if counts.contains(&3) && counts.contains(&2) => FULL_HOUSE,
+        (_, true, _) => FLUSH,
+        (Some(_), _, _) => STRAIGHT,
+        (_, _, Some(3)) => THREE_OF_A_KIND,
+        // high card, one pair and two pair are 0, 1 and 2
+        _ => counts.iter().filter(|&&count| count == 2).count(),
+    };
+    Hand {
+        cards: cards.iter().map(|card| card.rank).collect(),
+        category,
+        ranks: match straight_high {
+            Some(high) if category == STRAIGHT || category == STRAIGHT_FLUSH => vec![high],
+            _ => ranks,
+        },
+    }
+}
+
+// The best 5 card hand out of any number of cards
+pub fn best(cards: &[PokerCard]) -> Option<Hand> {
+    let mut best: Option<Hand> = None;
+    let n = cards.len();
+    for a in 0..n {
+        for b in a + 1..n {
+            for c in b + 1..n {
+                for d in c + 1..n {
+                    for e in d + 1..n {
+                        let hand = evaluate([cards[a], cards[b], cards[c], cards[d], cards[e]]);
+                        if best.as_ref().is_none_or(|best| hand > *best) {
+                            best = Some(hand);
+                        }
+                    }
+                }
+            }
+        }
+    }
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    // Counts every category over all 2,598,960 hands and compares with the known
+    // totals, along with the 7462 distinct hand strengths
+    #[test]
+    fn every_hand_is_counted() {
+        const EXPECTED: [u32; 9] = [
+            1_302_540, 1_098_240, 123_552, 54_912, 10_200, 5_108, 3_744, 624, 40,
+        ];
+        let deck = Suit::ALL
+            .iter()
+            .flat_map(|&suit| {
+                (0..Card::COUNT as u8).map(move |value| PokerCard {
+                    rank: Card::from_value(value),
+                    suit,
+                })
+            })
+            .collect::<Vec<_>>();
+        let mut counts = [0; 9];
+        let mut strengths = HashSet::new();
+        let n = deck.len();
+        for a in 0..n {
+            for b in a + 1..n {
+                for c in b + 1..n {
+                    for d in c + 1..n {
+                        for e in d + 1..n {
+                            let hand = evaluate([deck[a], deck[b], deck[c], deck[d], deck[e]]);
+                            counts[hand.category] += 1;
+                            strengths.insert((hand.category, hand.ranks));
+                        }
+                    }
+                }
+            }
+        }
+        for ((name, count), expected) in CATEGORIES.iter().zip(counts).zip(EXPECTED) {
+            assert_eq!(count, expected, "{}", name);
+        }
+        assert_eq!(strengths.len(), 7462);
+    }
+}
@@ -0,0 +1,111 @@
+use std::fmt::Write;
+
+use super::{Node, Pattern};
+
+const COLOURS: [&str; 6] = ["red", "blue", "darkgreen", "orange", "purple", "brown"];
+
+// A walk to highlight, as the nodes it visits in order
+pub struct Overlay {
+    pub label: String,
+    pub nodes: Vec<usize>,
+}
+
+// Start nodes are green and end nodes red, edges walked by an overlay take the
+// colour of the first overlay that uses them
+pub fn dot(nodes: &[Node], start: &Pattern, end: &Pattern, overlays: &[Overlay]) -> String {
+    let colour = |from: usize, to: usize| {
+        overlays
+            .iter()
+            .zip(COLOURS.iter().cycle())
+            .find(|(overlay, _)| overlay.nodes.windows(2).any(|w| w == [from, to]))
+            .map(|(_, colour)| *colour)
+    };
+    let mut dot = String::from("digraph network {\n");
+    for node in nodes {
+        if start.matches(node.name) {
+            writeln!(
+                dot,
+                "    {} [style=filled, fillcolor=palegreen];",
+                string(node.name)
+            )
+            .unwrap();
+        } else if end.matches(node.name) {
+            writeln!(
+                dot,
+                "    {} [style=filled, fillcolor=lightcoral];",
+                string(node.name)
+            )
+            .unwrap();
+        }
+    }
+    for (i, node) in nodes.iter().enumerate() {
+        let edges = if node.left == node.right {
+            vec![(node.left, "LR")]
+        } else {
+            vec![(node.left, "L"), (node.right, "R")]
+        };
+        for (to, label) in edges {
+            write!(
+                dot,
+                "    {} -> {} [label={}",
+                string(node.name),
+                string(nodes[to].name),
+                label
+            )
+            .unwrap();
+            if let Some(colour) = colour(i, to) {
+                write!(dot, ", color={}, penwidth=2", colour).unwrap();
+            }
+            dot += "];\n";
+        }
+    }
+    for (overlay, colour) in overlays.iter().zip(COLOURS.iter().cycle()) {
+        writeln!(dot, "    // {}: {}", colour, overlay.label).unwrap();
+    }
+    dot += "}\n";
+    dot
+}
+
+// Quoted for both formats, so DOT takes names that start with a digit
+fn string(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn names(nodes: &[Node], ids: &[usize]) -> String {
+    let names = ids
+        .iter()
+        .map(|&id| string(nodes[id].name))
+        .collect::<Vec<_>>();
+    format!("[{}]", names.join(","))
+}
+
+pub fn json(nodes: &[Node], start: &Pattern, end: &Pattern, overlays: &[Overlay]) -> String {
+    let nodes_json = nodes
+        .iter()
+        .map(|node| {
+            format!(
+                "{{\"name\":{},\"left\":{},\"right\":{},\"start\":{},\"end\":{}}}",
+                string(node.name),
+                string(nodes[node.left].name),
+                string(nodes[node.right].name),
+                start.matches(node.name),
+                end.matches(node.name)
+            )
+        })
+        .collect::<Vec<_>>();
+    let overlays_json = overlays
+        .iter()
+        .map(|overlay| {
+            format!(
+                "{{\"label\":{},\"nodes\":{}}}",
+                string(&overlay.label),
+                names(nodes, &overlay.nodes)
+            )
+        })
+        .collect::<Vec<_>>();
+    format!(
+        "{{\"nodes\":[{}],\"overlays\":[{}]}}",
+        nodes_json.join(","),
+        overlays_json.join(",")
+    )
+}
@@ -0,0 +1,89 @@
+use std::collections::HashSet;
+
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+
+const PRIMES: [usize; 11] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31];
+
+// `size` steps and `difficulty` ghosts, each on a loop of the step count times
+// its own prime: following the steps goes round the loop and lands on its Z
+// node only at the end of every lap, taking a wrong turn leads into a tangle of
+// decoys. The first ghost starts on AAA and its Z node is ZZZ.
+pub fn generate(seed: u64, size: usize, difficulty: usize) -> String {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let len = size.max(1);
+    let steps = (0..len)
+        .map(|_| if rng.gen() { 'L' } else { 'R' })
+        .collect::<String>();
+    let mut primes = PRIMES.to_vec();
+    primes.shuffle(&mut rng);
+    primes.truncate(difficulty.clamp(1, PRIMES.len()));
+
+    let loops = primes.iter().map(|prime| prime * len).sum::<usize>();
+    let decoys = len;
+    // names grow past three characters when the network needs it
+    let mut name_len = 3;
+    while 36usize.pow(name_len - 1) * 34 < 4 * (loops + decoys) {
+        name_len += 1;
+    }
+    let mut taken = HashSet::from(["AAA".to_string(), "ZZZ".to_string()]);
+    let mut name = |rng: &mut StdRng, last| name(rng, &mut taken, name_len, last);
+
+    let decoy_names = (0..decoys)
+        .map(|_| name(&mut rng, None))
+        .collect::<Vec<_>>();
+    let mut lines = Vec::new();
+    for (ghost, prime) in primes.iter().enumerate() {
+        let laps = prime * len;
+        let (start, end) = match ghost {
+            0 => ("AAA".to_string(), "ZZZ".to_string()),
+            _ => (name(&mut rng, Some('A')), name(&mut rng, Some('Z'))),
+        };
+        let ring = (0..laps)
+            .map(|j| match j {
+                j if j == laps - 1 => end.clone(),
+                _ => name(&mut rng, None),
+            })
+            .collect::<Vec<_>>();
+        lines.push(format!("{} = ({}, {})", start, ring[0], ring[0]));
+        // the node `j` along the ring is left on step `j + 1`
+        for j in 0..laps {
+            let next = ring[(j + 1) % laps].clone();
+            let decoy = decoy_names.choose(&mut rng).unwrap().clone();
+            let (left, right) = match steps.as_bytes()[(j + 1) % len] {
+                b'L' => (next, decoy),
+                _ => (decoy, next),
+            };
+            lines.push(format!("{} = ({}, {})", ring[j], left, right));
+        }
+    }
+    for decoy in &decoy_names {
+        let left = decoy_names.choose(&mut rng).unwrap();
+        let right = decoy_names.choose(&mut rng).unwrap();
+        lines.push(format!("{} = ({}, {})", decoy, left, right));
+    }
+    lines.shuffle(&mut rng);
+    format!("{}\n\n{}\n", steps, lines.join("\n"))
+}
+
+// A fresh random name, ending in `last` if given and never in A or Z otherwise
+fn name(rng: &mut StdRng, taken: &mut HashSet<String>, len: u32, last: Option<char>) -> String {
+    const ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789";
+    loop {
+        let mut name = (0..len)
+            .map(|_| *ALPHABET.choose(rng).unwrap() as char)
+            .collect::<String>();
+        match last {
+            Some(last) => {
+                name.pop();
+                name.push(last);
+            }
+            None if name.ends_with(['A', 'Z']) => continue,
+            None => {}
+        }
+        if taken.insert(name.clone()) {
+            return name;
+        }
+    }
+}
@@ -0,0 +1,53 @@
+use super::{Node, Step};
+
+// Where every (node, step index) state ends up after 2^level steps, with just
+// enough levels that any number of steps up to `max` is one lookup per level
+pub struct Jumps {
+    len: usize,
+    levels: Vec<Vec<u32>>,
+}
+
+impl Jumps {
+    pub fn new(nodes: &[Node], steps: &[Step], max: u64) -> Result<Self, String> {
+        let len = steps.len();
+        let states = nodes.len() * len;
+        if u32::try_from(states).is_err() {
+            return Err(format!("{} jump states don't fit in a u32", states));
+        }
+        let first = (0..states)
+            .map(|state| {
+                let (node, i) = (state / len, state % len);
+                (nodes[node].next(steps[i]) * len + (i + 1) % len) as u32
+            })
+            .collect::<Vec<_>>();
+        let mut levels = vec![first];
+        while levels.len() < (u64::BITS - max.leading_zeros()) as usize {
+            let last = &levels[levels.len() - 1];
+            let next = last.iter().map(|&state| last[state as usize]).collect();
+            levels.push(next);
+        }
+        Ok(Self { len, levels })
+    }
+
+    // The node and step index after `k` more steps from `node` at step `i`,
+    // `k` can't be more than the tables were built for
+    pub fn jump(&self, node: usize, i: usize, k: u64) -> (usize, usize) {
+        assert!(
+            u64::BITS - k.leading_zeros() <= self.levels.len() as u32,
+            "{} steps is past the jump tables",
+            k
+        );
+        let mut state = (node * self.len + i) as u32;
+        for (level, table) in self.levels.iter().enumerate() {
+            if k >> level & 1 == 1 {
+                state = table[state as usize];
+            }
+        }
+        (state as usize / self.len, state as usize % self.len)
+    }
+
+    // The node reached after `k` steps from `node` at the start of the steps
+    pub fn after(&self, node: usize, k: u64) -> usize {
+        self.jump(node, 0, k).0
+    }
+}
@@ -1,50 +1,175 @@
+use std::collections::HashMap;
 use std::str::Lines;
 
 use nom::bytes::complete::tag;
 use nom::character::complete::alphanumeric1;
 use nom::character::complete::space0;
+use nom::combinator::all_consuming;
 use nom::combinator::map;
 use nom::sequence::delimited;
 use nom::sequence::tuple;
 use nom::IResult;
+use num::integer::ExtendedGcd;
 use num::Integer;
 
-fn main() {
+mod export;
+mod generate;
+mod jumps;
+#[cfg(test)]
+mod oracle;
+mod pattern;
+
+use pattern::Pattern;
+
+fn main() -> Result<(), String> {
+    let args = std::env::args().collect::<Vec<_>>();
+    let generated = common::generated(&args, 283, 6, generate::generate)?;
+    let input = generated.as_deref().unwrap_or(INPUT);
+    if args.iter().any(|arg| arg == "--print") {
+        print!("{}", input);
+        return Ok(());
+    }
+    if args.iter().any(|arg| arg == "--dot" || arg == "--json") {
+        // --from and --to pick the highlighted nodes here, not a walk
+        let pattern = |flag: &str, default: &str| match args.iter().position(|arg| arg == flag) {
+            Some(i) => args
+                .get(i + 1)
+                .map(|arg| Pattern::parse(arg))
+                .ok_or_else(|| format!("usage: {} <pattern>", flag)),
+            None => Ok(Pattern::Suffix(default.to_string())),
+        };
+        let (start, goal) = (pattern("--from", "A")?, pattern("--to", "Z")?);
+        let (steps, nodes) = parse(input)?;
+        let mut overlays = Vec::new();
+        if args.iter().any(|arg| arg == "--path") {
+            let start = nodes
+                .iter()
+                .position(|n| n.name == "AAA")
+                .ok_or("no AAA node")?;
+            let walk = Walk::new(&nodes, &steps, start);
+            let end = walk
+                .nodes
+                .iter()
+                .position(|&node| nodes[node].name == "ZZZ")
+                .map_or(walk.nodes.len(), |end| end + 1);
+            overlays.push(export::Overlay {
+                label: "AAA to ZZZ".to_string(),
+                nodes: walk.nodes[..end].to_vec(),
+            });
+        }
+        if args.iter().any(|arg| arg == "--cycles") {
+            for i in (0..nodes.len()).filter(|&i| start.matches(nodes[i].name)) {
+                let walk = Walk::new(&nodes, &steps, i);
+                // close the loop so the edge back into the cycle is drawn too
+                let mut cycle = walk.nodes[walk.cycle_start..].to_vec();
+                cycle.push(walk.nodes[walk.cycle_start]);
+                overlays.push(export::Overlay {
+                    label: format!("{} cycle", nodes[i].name),
+                    nodes: cycle,
+                });
+            }
+        }
+        if args.iter().any(|arg| arg == "--dot") {
+            print!("{}", export::dot(&nodes, &start, &goal, &overlays));
+        } else {
+            println!("{}", export::json(&nodes, &start, &goal, &overlays));
+        }
+        return Ok(());
+    }
+    if let Some(i) = args.iter().position(|arg| arg == "--from") {
+        let usage = "usage: --from <pattern> --to <pattern>";
+        let start = Pattern::parse(args.get(i + 1).ok_or(usage)?);
+        let to = args.iter().position(|arg| arg == "--to").ok_or(usage)?;
+        let goal = Pattern::parse(args.get(to + 1).ok_or(usage)?);
+        let (steps, nodes) = parse(input)?;
+        let passes = Passes::new(&nodes, &steps, |node| goal.matches(node.name));
+        for (i, node) in nodes.iter().enumerate() {
+            if start.matches(node.name) {
+                match steps_to(&nodes, &passes, i, &goal) {
+                    Ok(count) => println!("{}: {} steps", node.name, count),
+                    Err(e) => println!("{}: {}", node.name, e),
+                }
+            }
+        }
+        println!(
+            "Together: {}",
+            match steps_together(&nodes, &steps, &start, &goal) {
+                Ok(count) => format!("{} steps", count),
+                Err(e) => e,
+            }
+        );
+        return Ok(());
+    }
+    if let Some(i) = args.iter().position(|arg| arg == "--jump") {
+        let usage = "usage: --jump <node> <steps>";
+        let name = args.get(i + 1).ok_or(usage)?;
+        let k = args
+            .get(i + 2)
+            .and_then(|k| k.parse::<u64>().ok())
+            .ok_or(usage)?;
+        let (steps, nodes) = parse(input)?;
+        let node = nodes
+            .iter()
+            .position(|n| n.name == name)
+            .ok_or_else(|| format!("no node {}", name))?;
+        let jumps = jumps::Jumps::new(&nodes, &steps, k)?;
+        println!(
+            "{} after {} steps: {}",
+            name,
+            k,
+            nodes[jumps.after(node, k)].name
+        );
+        return Ok(());
+    }
+    if args.iter().any(|arg| arg == "--bench") {
+        bench(input)?;
+        return Ok(());
+    }
+    if args.iter().any(|arg| arg == "--verify") {
+        verify(input)?;
+        return Ok(());
+    }
     let now = std::time::Instant::now();
-    let result = part1(INPUT);
+    let result = part1(input)?;
     println!("Part 1: {} [{:?}]", result, now.elapsed());
     let now = std::time::Instant::now();
-    let result = part2(INPUT);
+    let result = part2(input)?;
     println!("Part 2: {} [{:?}]", result, now.elapsed());
+    Ok(())
 }
 
 const INPUT: &str = include_str!("../input.txt");
 
 #[derive(Debug, Clone, Copy)]
-struct Node<'a> {
+pub(crate) struct Node<'a> {
     name: &'a str,
     left: usize,
     right: usize,
 }
 
 #[derive(Debug, Clone, Copy)]
-enum Step {
+pub(crate) enum Step {
     Left,
     Right,
 }
 
-fn steps(line: &str) -> Vec<Step> {
-    line.chars()
+fn steps(line: &str) -> Result<Vec<Step>, String> {
+    let steps = line
+        .chars()
         .map(|c| match c {
-            'L' => Step::Left,
-            'R' => Step::Right,
-            _ => panic!("Invalid character: {}", c),
+            'L' => Ok(Step::Left),
+            'R' => Ok(Step::Right),
+            _ => Err(format!("line 1: invalid step {:?}", c)),
         })
-        .collect::<Vec<_>>()
+        .collect::<Result<Vec<_>, _>>()?;
+    if steps.is_empty() {
+        return Err("line 1: no steps".to_string());
+    }
+    Ok(steps)
 }
 
 fn node_parser(input: &str) -> IResult<&str, (&str, (&str, &str))> {
-    tuple((
+    all_consuming(tuple((
         delimited(space0, alphanumeric1, tag(" = ")),
         delimited(
             tag("("),
@@ -54,69 +179,472 @@ fn node_parser(input: &str) -> IResult<&str, (&str, (&str, &str))> {
             ),
             tag(")"),
         ),
-    ))(input)
+    )))(input)
 }
 
-fn nodes(lines: Lines) -> Vec<Node> {
-    let vec = lines
-        .map(|line| node_parser(line).unwrap().1)
-        .collect::<Vec<_>>();
-    let vec = vec
-        .iter()
-        .map(|(name, (x, y))| {
-            Node {
+struct Label<'a> {
+    name: &'a str,
+    edges: Option<(usize, usize)>,
+    // where the node is defined, or first referenced while it isn't
+    line: usize,
+}
+
+// Labels are interned as they are first seen, so edges can point forwards
+fn nodes<'a>(lines: Lines<'a>) -> Result<Vec<Node<'a>>, String> {
+    let mut ids = HashMap::new();
+    let mut labels = Vec::new();
+    let mut intern = |name, line, labels: &mut Vec<Label<'a>>| {
+        *ids.entry(name).or_insert_with(|| {
+            labels.push(Label {
                 name,
-                left: vec.iter().position(|(n, _)| n == x).unwrap(),
-                right: vec.iter().position(|(n, _)| n == y).unwrap(),
-            }
+                edges: None,
+                line,
+            });
+            labels.len() - 1
         })
-        .collect::<Vec<_>>();
-    vec
+    };
+    // the network starts after the steps and a blank line
+    for (line_no, line) in lines.enumerate().map(|(i, line)| (i + 3, line)) {
+        let (_, (name, (left, right))) =
+            node_parser(line).map_err(|_| format!("line {}: invalid node {:?}", line_no, line))?;
+        let id = intern(name, line_no, &mut labels);
+        let left = intern(left, line_no, &mut labels);
+        let right = intern(right, line_no, &mut labels);
+        let label = &mut labels[id];
+        if label.edges.is_some() {
+            return Err(format!(
+                "line {}: node {} is already defined on line {}",
+                line_no, name, label.line
+            ));
+        }
+        label.edges = Some((left, right));
+        label.line = line_no;
+    }
+    labels
+        .into_iter()
+        .map(|label| match label.edges {
+            Some((left, right)) => Ok(Node {
+                name: label.name,
+                left,
+                right,
+            }),
+            None => Err(format!(
+                "line {}: edge points to missing node {}",
+                label.line, label.name
+            )),
+        })
+        .collect()
 }
 
-fn part1(input: &str) -> u64 {
+impl Node<'_> {
+    fn next(&self, step: Step) -> usize {
+        match step {
+            Step::Left => self.left,
+            Step::Right => self.right,
+        }
+    }
+}
+
+// Whether any goal node can be reached at all, whatever the steps are
+fn reachable(nodes: &[Node], start: usize, goal: impl Fn(&Node) -> bool) -> bool {
+    let mut seen = vec![false; nodes.len()];
+    let mut stack = vec![start];
+    while let Some(node) = stack.pop() {
+        if goal(&nodes[node]) {
+            return true;
+        }
+        if !std::mem::replace(&mut seen[node], true) {
+            stack.extend([nodes[node].left, nodes[node].right]);
+        }
+    }
+    false
+}
+
+pub(crate) fn parse(input: &str) -> Result<(Vec<Step>, Vec<Node<'_>>), String> {
     let mut lines = input.lines();
-    let steps = steps(lines.next().unwrap());
-    let mut steps = steps.iter().cycle();
+    let steps = steps(lines.next().unwrap_or_default())?;
+    if let Some(line) = lines.next().filter(|line| !line.trim().is_empty()) {
+        return Err(format!("line 2: expected a blank line, found {:?}", line));
+    }
+    Ok((steps, nodes(lines)?))
+}
+
+// What one full pass of the steps does from each node: where it ends up, and
+// how many steps into the pass it is on a goal node
+struct Passes {
+    len: u64,
+    end: Vec<usize>,
+    hits: Vec<Vec<u64>>,
+}
+
+impl Passes {
+    fn new(nodes: &[Node], steps: &[Step], goal: impl Fn(&Node) -> bool) -> Self {
+        let (end, hits) = (0..nodes.len())
+            .map(|start| {
+                let mut node = start;
+                let mut hits = Vec::new();
+                for (i, &step) in steps.iter().enumerate() {
+                    if goal(&nodes[node]) {
+                        hits.push(i as u64);
+                    }
+                    node = nodes[node].next(step);
+                }
+                (node, hits)
+            })
+            .unzip();
+        Self {
+            len: steps.len() as u64,
+            end,
+            hits,
+        }
+    }
+}
+
+// Steps from `start` until the first node matching `goal`, a whole pass at a time
+fn steps_to(nodes: &[Node], passes: &Passes, start: usize, goal: &Pattern) -> Result<u64, String> {
+    let name = nodes[start].name;
+    if !reachable(nodes, start, |node| goal.matches(node.name)) {
+        return Err(format!("{} unreachable from {}", goal, name));
+    }
+    let mut seen = vec![false; nodes.len()];
+    let mut node = start;
     let mut steps_taken = 0;
-    lines.next();
-    let nodes = nodes(lines);
-    let node = nodes.iter().find(|n| n.name == "AAA");
-    let mut node = node.unwrap().clone();
-    while node.name != "ZZZ" {
-        let step = steps.next().unwrap();
-        steps_taken += 1;
-        node = match step {
-            Step::Left => nodes[node.left],
-            Step::Right => nodes[node.right],
+    loop {
+        if let Some(hit) = passes.hits[node].first() {
+            return Ok(steps_taken + hit);
         }
+        if std::mem::replace(&mut seen[node], true) {
+            return Err(format!(
+                "{} cycles without reaching {} after {} steps",
+                name, goal, steps_taken
+            ));
+        }
+        node = passes.end[node];
+        steps_taken += passes.len;
     }
-    steps_taken
 }
 
-fn part2(input: &str) -> u64 {
-    let mut lines = input.lines();
-    let steps = steps(lines.next().unwrap());
-    lines.next();
-    let nodes = nodes(lines);
-    let start_nodes = nodes
+pub(crate) fn part1(input: &str) -> Result<u64, String> {
+    let (steps, nodes) = parse(input)?;
+    let start = nodes
         .iter()
-        .filter(|n| n.name.ends_with('A'))
-        .collect::<Vec<_>>();
-    let mut uwu = Vec::new();
-    for node in start_nodes {
-        let mut steps = steps.iter().copied().cycle();
-        let mut node = node;
-        let mut steps_taken = 0;
-        while !node.name.ends_with('Z') {
-            let step = steps.next().unwrap();
-            steps_taken += 1;
-            node = match step {
-                Step::Left => &nodes[node.left],
-                Step::Right => &nodes[node.right],
+        .position(|n| n.name == "AAA")
+        .ok_or("no AAA node")?;
+    let goal = Pattern::Exact("ZZZ".to_string());
+    let passes = Passes::new(&nodes, &steps, |node| goal.matches(node.name));
+    steps_to(&nodes, &passes, start, &goal)
+}
+
+// Every node visited from a start until a (node, step index) state repeats,
+// after the last one the walk loops back to `nodes[cycle_start]`
+struct Walk {
+    nodes: Vec<usize>,
+    cycle_start: usize,
+}
+
+impl Walk {
+    fn new(nodes: &[Node], steps: &[Step], start: usize) -> Self {
+        let mut seen = vec![None; nodes.len() * steps.len()];
+        let mut visited = Vec::new();
+        let mut node = start;
+        let cycle_start = loop {
+            let i = visited.len() % steps.len();
+            if let Some(first) = seen[node * steps.len() + i] {
+                break first;
             }
+            seen[node * steps.len() + i] = Some(visited.len());
+            visited.push(node);
+            node = nodes[node].next(steps[i]);
+        };
+        Self {
+            nodes: visited,
+            cycle_start,
         }
-        uwu.push(steps_taken);
     }
-    uwu.iter().fold(1, |acc, x| acc.lcm(x))
+}
+
+// Step counts at which a walk is on a goal node: every value in `once`, and
+// every value in `repeating` plus any multiple of `period`
+#[derive(Debug, Clone)]
+struct Arrivals {
+    once: Vec<u64>,
+    repeating: Vec<u64>,
+    period: u64,
+}
+
+impl Arrivals {
+    // Follows whole passes from `start` until a pass starts on a node again
+    fn new(passes: &Passes, start: usize) -> Self {
+        let mut seen = vec![None; passes.end.len()];
+        let mut starts = Vec::new();
+        let mut node = start;
+        let cycle_start = loop {
+            if let Some(first) = seen[node] {
+                break first as u64;
+            }
+            seen[node] = Some(starts.len());
+            starts.push(node);
+            node = passes.end[node];
+        };
+        let (once, repeating) = starts
+            .iter()
+            .enumerate()
+            .flat_map(|(pass, &node)| {
+                passes.hits[node]
+                    .iter()
+                    .map(move |&hit| pass as u64 * passes.len + hit)
+            })
+            .partition(|&time| time < cycle_start * passes.len);
+        Self {
+            once,
+            repeating,
+            period: (starts.len() as u64 - cycle_start) * passes.len,
+        }
+    }
+
+    // The same from a step by step walk, kept to benchmark against
+    fn walking(walk: &Walk, nodes: &[Node], goal: impl Fn(&Node) -> bool) -> Self {
+        let (once, repeating) = (0..walk.nodes.len() as u64)
+            .filter(|&time| goal(&nodes[walk.nodes[time as usize]]))
+            .partition(|&time| time < walk.cycle_start as u64);
+        Self {
+            once,
+            repeating,
+            period: (walk.nodes.len() - walk.cycle_start) as u64,
+        }
+    }
+
+    fn contains(&self, time: u64) -> bool {
+        self.once.contains(&time)
+            || self
+                .repeating
+                .iter()
+                .any(|&first| time >= first && (time - first).is_multiple_of(self.period))
+    }
+
+    // Arrival times shared by both walks, periods combine through the CRT
+    fn intersect(&self, other: &Self) -> Result<Self, String> {
+        let gcd = self.period.gcd(&other.period);
+        let period = (self.period / gcd)
+            .checked_mul(other.period)
+            .ok_or("combined period overflows")?;
+        let mut once = self
+            .once
+            .iter()
+            .filter(|&&time| other.contains(time))
+            .chain(other.once.iter().filter(|&&time| self.contains(time)))
+            .copied()
+            .collect::<Vec<_>>();
+        once.sort_unstable();
+        once.dedup();
+        let mut repeating = Vec::new();
+        for &a in &self.repeating {
+            for &b in &other.repeating {
+                if let Some(time) = crt(a, self.period, b, other.period) {
+                    // first shared arrival once both walks are in their cycles, in
+                    // u128 as the period can be close to u64::MAX
+                    let (time, from, wide) = (time as u128, a.max(b) as u128, period as u128);
+                    let first = from + (time + wide - from % wide) % wide;
+                    repeating.push(u64::try_from(first).map_err(|_| "combined period overflows")?);
+                }
+            }
+        }
+        repeating.sort_unstable();
+        repeating.dedup();
+        Ok(Self {
+            once,
+            repeating,
+            period,
+        })
+    }
+
+    fn first(&self) -> Option<u64> {
+        self.once.iter().chain(&self.repeating).min().copied()
+    }
+}
+
+// Smallest `t` with `t = a (mod m)` and `t = b (mod n)`, if there is one,
+// lcm(m, n) has to fit in a u64
+fn crt(a: u64, m: u64, b: u64, n: u64) -> Option<u64> {
+    let (a, m, b, n) = (a as i128, m as i128, b as i128, n as i128);
+    let ExtendedGcd { gcd, x, .. } = m.extended_gcd(&n);
+    if (b - a) % gcd != 0 {
+        return None;
+    }
+    let modulus = n / gcd;
+    let k = (b - a) / gcd;
+    let k = k.rem_euclid(modulus) as u128 * x.rem_euclid(modulus) as u128 % modulus as u128;
+    Some((a + k as i128 * m).rem_euclid(m * modulus) as u64)
+}
+
+// Steps until a walk from every node matching `start` is on a `goal` node at once
+fn steps_together(
+    nodes: &[Node],
+    steps: &[Step],
+    start: &Pattern,
+    goal: &Pattern,
+) -> Result<u64, String> {
+    let goal_node = |node: &Node| goal.matches(node.name);
+    let passes = Passes::new(nodes, steps, goal_node);
+    let mut ghosts = (0..nodes.len())
+        .filter(|&i| start.matches(nodes[i].name))
+        .enumerate()
+        .map(|(ghost, start)| {
+            let name = nodes[start].name;
+            if !reachable(nodes, start, goal_node) {
+                return Err(format!(
+                    "ghost {} ({}) can't reach {}",
+                    ghost + 1,
+                    name,
+                    goal
+                ));
+            }
+            let arrivals = Arrivals::new(&passes, start);
+            if arrivals.first().is_none() {
+                return Err(format!(
+                    "ghost {} ({}) cycles without visiting {}",
+                    ghost + 1,
+                    name,
+                    goal
+                ));
+            }
+            Ok(arrivals)
+        });
+    let first = ghosts
+        .next()
+        .ok_or_else(|| format!("no start node matches {}", start))??;
+    ghosts
+        .try_fold(first, |acc, ghost| acc.intersect(&ghost?))?
+        .first()
+        .ok_or_else(|| format!("the ghosts never all reach {} at once", goal))
+}
+
+pub(crate) fn part2(input: &str) -> Result<u64, String> {
+    let (steps, nodes) = parse(input)?;
+    steps_together(
+        &nodes,
+        &steps,
+        &Pattern::Suffix("A".to_string()),
+        &Pattern::Suffix("Z".to_string()),
+    )
+}
+
+// Jumps every walk straight to the claimed answers and checks where it lands
+fn verify(input: &str) -> Result<(), String> {
+    let (steps, nodes) = parse(input)?;
+    let start = nodes
+        .iter()
+        .position(|n| n.name == "AAA")
+        .ok_or("no AAA node")?;
+    let (answer, together) = (part1(input)?, part2(input)?);
+    let jumps = jumps::Jumps::new(&nodes, &steps, answer.max(together))?;
+    let end = nodes[jumps.after(start, answer)].name;
+    println!("Part 1: AAA after {} steps is on {}", answer, end);
+    if end != "ZZZ" {
+        return Err(format!("part 1 answer {} doesn't end on ZZZ", answer));
+    }
+    let answer = together;
+    for start in (0..nodes.len()).filter(|&i| nodes[i].name.ends_with('A')) {
+        let end = nodes[jumps.after(start, answer)].name;
+        println!(
+            "Part 2: {} after {} steps is on {}",
+            nodes[start].name, answer, end
+        );
+        if !end.ends_with('Z') {
+            return Err(format!(
+                "part 2 answer {} leaves {} on {}",
+                answer, nodes[start].name, end
+            ));
+        }
+    }
+    Ok(())
+}
+
+// Times whole-pass jumps against walking one step at a time
+fn bench(input: &str) -> Result<(), String> {
+    let (steps, nodes) = parse(input)?;
+    let start = nodes
+        .iter()
+        .position(|n| n.name == "AAA")
+        .ok_or("no AAA node")?;
+
+    let now = std::time::Instant::now();
+    let mut node = start;
+    let mut walked = 0;
+    for &step in steps.iter().cycle() {
+        if nodes[node].name == "ZZZ" {
+            break;
+        }
+        node = nodes[node].next(step);
+        walked += 1;
+    }
+    let elapsed = now.elapsed();
+    println!("Part 1 step by step: {} [{:?}]", walked, elapsed);
+    let goal = Pattern::Exact("ZZZ".to_string());
+    let now = std::time::Instant::now();
+    let passes = Passes::new(&nodes, &steps, |node| goal.matches(node.name));
+    let built = now.elapsed();
+    let now = std::time::Instant::now();
+    let jumped = steps_to(&nodes, &passes, start, &goal)?;
+    println!(
+        "Part 1 whole passes: {} [{:?} + {:?} building the table]",
+        jumped,
+        now.elapsed(),
+        built
+    );
+    if walked != jumped {
+        return Err(format!("part 1 differs: {} and {}", walked, jumped));
+    }
+
+    let is_end = |node: &Node| node.name.ends_with('Z');
+    let starts = (0..nodes.len())
+        .filter(|&i| nodes[i].name.ends_with('A'))
+        .collect::<Vec<_>>();
+    let now = std::time::Instant::now();
+    let walked = starts
+        .iter()
+        .map(|&start| Arrivals::walking(&Walk::new(&nodes, &steps, start), &nodes, is_end))
+        .collect::<Vec<_>>();
+    let elapsed = now.elapsed();
+    println!("Part 2 arrivals step by step: [{:?}]", elapsed);
+    let now = std::time::Instant::now();
+    let passes = Passes::new(&nodes, &steps, is_end);
+    let jumped = starts
+        .iter()
+        .map(|&start| Arrivals::new(&passes, start))
+        .collect::<Vec<_>>();
+    println!("Part 2 arrivals whole passes: [{:?}]", now.elapsed());
+    for ((walked, jumped), &start) in walked.iter().zip(&jumped).zip(&starts) {
+        let (a, b) = (walked.first(), jumped.first());
+        if a != b || walked.period != jumped.period {
+            return Err(format!("part 2 differs from {}", nodes[start].name));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn arrivals(repeating: u64, period: u64) -> Arrivals {
+        Arrivals {
+            once: Vec::new(),
+            repeating: vec![repeating],
+            period,
+        }
+    }
+
+    #[test]
+    fn intersect_past_2_63() {
+        let period = (1 << 63) - 1;
+        let both = arrivals(1 << 63, period)
+            .intersect(&arrivals(1, 2))
+            .unwrap();
+        assert_eq!(both.period, 2 * period);
+        assert_eq!(both.first(), Some((1 << 63) + period));
+        // the first shared arrival would be past u64::MAX
+        let late = arrivals(u64::MAX - 1, period).intersect(&arrivals(1, 2));
+        assert_eq!(late.err().as_deref(), Some("combined period overflows"));
+    }
 }
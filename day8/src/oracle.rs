@@ -0,0 +1,113 @@
+use std::collections::{HashMap, HashSet};
+
+use proptest::prelude::*;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use super::{generate, jumps};
+
+// Slow but obviously right: every ghost walked one step at a time until they
+// all stand on a goal together, or until they are back where they were at the
+// same point in the steps and never will
+fn walk(input: &str, start: impl Fn(&str) -> bool, goal: impl Fn(&str) -> bool) -> Option<u64> {
+    let mut lines = input.lines();
+    let steps = lines.next().unwrap();
+    let network = lines
+        .skip(1)
+        .map(|line| (&line[..line.find(' ').unwrap()], line))
+        .collect::<HashMap<_, _>>();
+    let mut ghosts = network
+        .keys()
+        .copied()
+        .filter(|name| start(name))
+        .collect::<Vec<_>>();
+    let mut seen = HashSet::new();
+    for (steps_taken, (i, step)) in steps.chars().enumerate().cycle().enumerate() {
+        if ghosts.iter().all(|name| goal(name)) {
+            return Some(steps_taken as u64);
+        }
+        if !seen.insert((i, ghosts.clone())) {
+            return None;
+        }
+        for ghost in &mut ghosts {
+            let (_, edges) = network[ghost].split_once(" = (").unwrap();
+            let (left, right) = edges.trim_end_matches(')').split_once(", ").unwrap();
+            *ghost = if step == 'L' { left } else { right };
+        }
+    }
+    unreachable!()
+}
+
+// A small random network with no structure at all, so walks can take a while
+// to reach their cycles, hit goals several times a lap or never meet
+fn tangle(seed: u64, nodes: usize, len: usize) -> String {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let steps = (0..len)
+        .map(|_| if rng.gen() { 'L' } else { 'R' })
+        .collect::<String>();
+    let names = (0..nodes)
+        .map(|i| match i {
+            0 => "AAA".to_string(),
+            1 => "ZZZ".to_string(),
+            i => format!("N{}{}", i, ['A', 'B', 'Z'][rng.gen_range(0..3)]),
+        })
+        .collect::<Vec<_>>();
+    let lines = names
+        .iter()
+        .map(|name| {
+            let left = &names[rng.gen_range(0..nodes)];
+            let right = &names[rng.gen_range(0..nodes)];
+            format!("{} = ({}, {})", name, left, right)
+        })
+        .collect::<Vec<_>>();
+    format!("{}\n\n{}\n", steps, lines.join("\n"))
+}
+
+fn check(input: &str) -> Result<(), TestCaseError> {
+    let slow = walk(input, |name| name == "AAA", |name| name == "ZZZ");
+    prop_assert_eq!(super::part1(input).ok(), slow, "part 1");
+    let slow = walk(
+        input,
+        |name| name.ends_with('A'),
+        |name| name.ends_with('Z'),
+    );
+    prop_assert_eq!(super::part2(input).ok(), slow, "part 2");
+    Ok(())
+}
+
+proptest! {
+    #[test]
+    fn solvers_match_oracle(seed: u64, size in 1..=4usize, difficulty in 1..=3usize) {
+        check(&generate::generate(seed, size, difficulty))?;
+    }
+
+    #[test]
+    fn solvers_match_oracle_on_tangles(seed: u64, nodes in 2..=8usize, len in 1..=4usize) {
+        check(&tangle(seed, nodes, len))?;
+    }
+
+    #[test]
+    fn jumps_match_walking(
+        seed: u64,
+        nodes in 2..=8usize,
+        len in 1..=4usize,
+        max in 0..2000u64,
+        k in 0..2000u64,
+    ) {
+        let input = tangle(seed, nodes, len);
+        let (steps, nodes) = super::parse(&input).map_err(TestCaseError::fail)?;
+        let jumps = jumps::Jumps::new(&nodes, &steps, max).map_err(TestCaseError::fail)?;
+        for k in [0, k.min(max), max] {
+            for start in 0..nodes.len() {
+                for i in 0..steps.len() {
+                    let (mut node, mut j) = (start, i);
+                    for _ in 0..k {
+                        node = nodes[node].next(steps[j]);
+                        j = (j + 1) % steps.len();
+                    }
+                    prop_assert_eq!(jumps.jump(start, i, k), (node, j), "k = {}", k);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,61 @@
+use std::fmt;
+
+// Picks out nodes by name, used for both the start and the goal of a walk
+#[derive(Debug, Clone)]
+pub enum Pattern {
+    Exact(String),
+    Suffix(String),
+    // `?` matches any one character and `*` any number of them
+    Glob(String),
+    Set(Vec<String>),
+}
+
+impl Pattern {
+    // `AAA` is an exact name, `*Z` a suffix, `A?*` a glob and `AAA,BBA` a set
+    pub fn parse(s: &str) -> Self {
+        if s.contains(',') {
+            Self::Set(s.split(',').map(str::to_string).collect())
+        } else if let Some(suffix) = s
+            .strip_prefix('*')
+            .filter(|rest| !rest.contains(['*', '?']))
+        {
+            Self::Suffix(suffix.to_string())
+        } else if s.contains(['*', '?']) {
+            Self::Glob(s.to_string())
+        } else {
+            Self::Exact(s.to_string())
+        }
+    }
+
+    pub fn matches(&self, name: &str) -> bool {
+        match self {
+            Self::Exact(exact) => name == exact,
+            Self::Suffix(suffix) => name.ends_with(suffix.as_str()),
+            Self::Glob(glob) => glob_matches(glob.as_bytes(), name.as_bytes()),
+            Self::Set(names) => names.iter().any(|n| n == name),
+        }
+    }
+}
+
+fn glob_matches(glob: &[u8], name: &[u8]) -> bool {
+    match (glob.first(), name.first()) {
+        (None, _) => name.is_empty(),
+        (Some(b'*'), _) => {
+            glob_matches(&glob[1..], name) || (!name.is_empty() && glob_matches(glob, &name[1..]))
+        }
+        (Some(b'?'), Some(_)) => glob_matches(&glob[1..], &name[1..]),
+        (Some(g), Some(n)) if g == n => glob_matches(&glob[1..], &name[1..]),
+        _ => false,
+    }
+}
+
+impl fmt::Display for Pattern {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Exact(exact) => write!(f, "{}", exact),
+            Self::Suffix(suffix) => write!(f, "a node ending in {}", suffix),
+            Self::Glob(glob) => write!(f, "a node matching {}", glob),
+            Self::Set(names) => write!(f, "any of {}", names.join(", ")),
+        }
+    }
+}